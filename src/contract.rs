@@ -7,9 +7,12 @@ use cosmwasm_std::entry_point;
 use thiserror::Error;
 
 use cosmwasm_std::{
-	attr, coins, to_binary, Addr, BankMsg, Binary, Deps, DepsMut, Env, MessageInfo,
-	Response, StdError, StdResult, Uint128, CosmosMsg, Coin,
+	attr, coins, to_binary, Addr, Api, Event, BankMsg, Binary, Deps, DepsMut, Env, MessageInfo,
+	Response, StdError, StdResult, Uint128, CosmosMsg, Coin, Decimal, Order, Storage,
+	ContractResult, QuerierWrapper, QueryRequest, SystemResult, to_vec,
 };
+use terra_cosmwasm::{TerraQuerier, TerraQuery, TerraQueryWrapper, TerraRoute};
+use crate::map::Map;
 
 use cw2::set_contract_version;
 use cw20_base::allowances::{
@@ -24,33 +27,182 @@ use cw20_base::contract::{
 	query_marketing_info, query_download_logo,
 };
 
-use cw20_base::state::{MinterData, TokenInfo, TOKEN_INFO, LOGO,  MARKETING_INFO,
+use cw20_base::state::{MinterData, TokenInfo, TOKEN_INFO, LOGO,  MARKETING_INFO, BALANCES,
 };
 use cw20::{
-	Logo, LogoInfo, MarketingInfoResponse,
+	Expiration, Logo, LogoInfo, MarketingInfoResponse,
 };
 use crate::curves::DecimalPlaces;
 use crate::error::ContractError;
 use crate::msg::{CurveFn, CurveInfoResponse, ParamInfoResponse, AcctInfoResponse,
-	DexferInfoResponse, SafetyInfoResponse, ExecuteMsg, InstantiateMsg,
+	DexferInfoResponse, SafetyInfoResponse, ReserveHealthResponse, LiquidityInfoResponse,
+	TaxAccrualResponse, ChainTaxInfoResponse, TaxAmount, TaxRecipientMsg, TaxRecipientInfo,
+	TaxRecipientsResponse, PendingTaxesResponse, AffiliateInfoResponse, AffiliatesResponse,
+	AffiliateTier, AffiliateTiersResponse, AffiliateRulesResponse, AffiliateDenylistResponse,
+	PendingOwnerResponse, Role, RoleAssignment, RolesResponse, PendingChangeInfo,
+	PendingChangesResponse, TradingStatus, PauseAction, PauseFlag, PauseStatusResponse,
+	BreakerWindow, CircuitBreakerStatusResponse, LimitsResponse, WhaleInfoResponse,
+	CooldownResponse, HolderTier, HolderTiersResponse, SimulateBuyResponse, HoldingTaxStep,
+	HoldingTaxResponse,
+	DenylistEntry, DenylistEntryInfo, ComplianceDenylistResponse, ExecuteMsg, InstantiateMsg,
+	InstantiateAcctConfig, InstantiateMarketingInfo,
 QueryMsg, MigrateMsg};
 
 use crate::state::{CurveState, CURVE_STATE, CURVE_TYPE,
-	PARAM_CONFIG, ParamConfig, ACCT_CONFIG, AcctConfig,
-	DEXFER_CONFIG, DexferConfig, SAFETY_CONFIG, SafetyConfig, };
+	PARAM_CONFIG, ParamConfig, PARAM_CEILINGS, ParamCeilings, ACCT_CONFIG, AcctConfig,
+	DEXFER_CONFIG, DexferConfig, SAFETY_CONFIG, SafetyConfig,
+	RESERVE_CONFIG, ReserveConfig, LIQUIDITY_CONFIG, LiquidityConfig,
+	SELL_TAX_ACCRUED, CHAIN_TAX_CONFIG, ChainTaxConfig, TAX_RECIPIENTS, TaxRecipient,
+	TAX_TOTALS, LEGACY_PARAM_CONFIG, LEGACY_ACCT_CONFIG,
+	UNCHECKED_ACCT_CONFIG, UNCHECKED_DEXFER_CONFIG, TAX_PENDING, DISTRIBUTION_CONFIG,
+	DistributionConfig, LAST_DISTRIBUTION, AFFILIATES, Affiliate, AFFILIATE_CODES,
+	AFFILIATE_TIERS, AFFILIATE_RULES, AffiliateRules, REFERRERS, AFFILIATE_DENYLIST,
+	PENDING_OWNER, OwnershipProposal, ROLES, TIMELOCK_CONFIG, TimelockConfig, PENDING_CHANGES,
+	PendingChange, PENDING_CHANGE_ID, LEGACY_SAFETY_CONFIG, PAUSED_ACTIONS,
+	COOLDOWN_CONFIG, CooldownConfig, LAST_BUY, HOLDER_TIERS, HOLDING_TAX_SCHEDULE, ACQUIRED_AT,
+	COMPLIANCE_DENYLIST, WHALE_CONFIG, WhaleConfig, WHALE_WHITELIST,
+	BUY_LIMITS, BuyLimits, ADDRESS_BUY_USAGE, BLOCK_BUY_USAGE, BuyUsage,
+	CIRCUIT_BREAKER_CONFIG, CircuitBreakerConfig, PRICE_SAMPLES, PriceSample, BREAKER_TRIP, BreakerTrip, };
+use crate::prefix::Bound;
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:cw20-bonding";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+pub fn migrate(mut deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+	// Before the N-way tax split the tax accounts lived in ParamConfig and AcctConfig
+	if TAX_RECIPIENTS.may_load(deps.storage)?.is_none() {
+		migrate_tax_recipients(deps.branch())?;
+	}
+	// Accounts used to be stored unchecked with "none" for unset
+	if has_unchecked_accounts(deps.as_ref())? {
+		migrate_accounts(deps.branch())?;
+	}
+	// can_buy/can_sell strings became TradingStatus
+	if let Ok(Some(legacy)) = LEGACY_SAFETY_CONFIG.may_load(deps.storage) {
+		let status = legacy_trading_status(deps.as_ref(), &legacy.can_buy, &legacy.can_sell);
+		SAFETY_CONFIG.save(deps.storage, &SafetyConfig { status })?;
+	}
 	Ok(Response::default())
 }
 
+/// Closest status to the old flags. A buy-only allowlist next to open sells becomes SellOnly
+fn legacy_trading_status(deps: Deps, can_buy: &str, can_sell: &str) -> TradingStatus {
+	if can_buy == "1" && can_sell == "1" {
+		return TradingStatus::Open;
+	}
+	if can_sell == "1" {
+		return TradingStatus::SellOnly;
+	}
+	let mut allowed: Vec<Addr> = vec![];
+	for flag in [can_buy, can_sell] {
+		if let Ok(addr) = deps.api.addr_validate(flag) {
+			if !allowed.contains(&addr) {
+				allowed.push(addr);
+			}
+		}
+	}
+	if allowed.is_empty() {
+		TradingStatus::Paused
+	} else {
+		TradingStatus::Allowlist(allowed)
+	}
+}
+
+/// Valid accounts are stored the same in both layouts, only one that doesn't validate is left to convert
+fn has_unchecked_accounts(deps: Deps) -> StdResult<bool> {
+	let accounts = UNCHECKED_ACCT_CONFIG.load(deps.storage)?;
+	let special = UNCHECKED_DEXFER_CONFIG.load(deps.storage)?;
+	Ok([
+		accounts.presale_acct,
+		accounts.stake_acct,
+		accounts.unstake_acct,
+		special.dexfer_manager,
+		special.tax_exempt,
+	]
+	.iter()
+	.flatten()
+	.any(|acct| deps.api.addr_validate(acct).is_err()))
+}
+
+fn migrate_accounts(deps: DepsMut) -> StdResult<()> {
+	let accounts = UNCHECKED_ACCT_CONFIG.load(deps.storage)?;
+	ACCT_CONFIG.save(deps.storage, &AcctConfig {
+		owner: accounts.owner,
+		presale_acct: checked_acct(deps.api, accounts.presale_acct),
+		stake_acct: checked_acct(deps.api, accounts.stake_acct),
+		unstake_acct: checked_acct(deps.api, accounts.unstake_acct),
+	})?;
+	
+	let special = UNCHECKED_DEXFER_CONFIG.load(deps.storage)?;
+	DEXFER_CONFIG.save(deps.storage, &DexferConfig {
+		dexfer_manager: checked_acct(deps.api, special.dexfer_manager),
+		deposit_percent: special.deposit_percent,
+		tax_exempt: checked_acct(deps.api, special.tax_exempt),
+		token_minter: special.token_minter,
+	})?;
+	Ok(())
+}
+
+/// An account that never validated could not have been used, so it is dropped rather than failing the migration
+fn checked_acct(api: &dyn Api, acct: Option<String>) -> Option<Addr> {
+	acct.and_then(|a| api.addr_validate(&a).ok())
+}
+
+fn migrate_tax_recipients(deps: DepsMut) -> StdResult<()> {
+	let params = LEGACY_PARAM_CONFIG.load(deps.storage)?;
+	let accounts = LEGACY_ACCT_CONFIG.load(deps.storage)?;
+	
+	// expense always took whatever the other shares left
+	let named_percent = params.yield_percent + params.burn_percent + params.social_percent;
+	if named_percent > 1000 {
+		return Err(StdError::generic_err(format!(
+			"Legacy tax shares add up to {} per mille, more than the whole tax",
+			named_percent
+		)));
+	}
+	let expense_percent = 1000 - named_percent;
+	let legacy = [
+		("yield", &accounts.yield_acct, params.yield_percent),
+		("burn", &accounts.burn_acct, params.burn_percent),
+		("social", &accounts.social_acct, params.social_percent),
+		("expense", &accounts.expense_acct, expense_percent),
+	];
+	
+	// without a tax the old accounts may never have been set
+	let mut recipients = vec![];
+	if params.tax_percent != 0 {
+		for (label, acct, share_per_mille) in legacy {
+			if share_per_mille == 0 {
+				continue;
+			}
+			recipients.push(TaxRecipient {
+				addr: Some(deps.api.addr_validate(acct)?),
+				share_per_mille,
+				label: label.to_string(),
+			});
+		}
+	}
+	TAX_RECIPIENTS.save(deps.storage, &recipients)?;
+	
+	PARAM_CONFIG.save(deps.storage, &ParamConfig {
+		affiliate_percent: params.affiliate_percent,
+		tax_percent: params.tax_percent,
+		presale_price: params.presale_price,
+	})?;
+	ACCT_CONFIG.save(deps.storage, &AcctConfig {
+		owner: Some(accounts.owner),
+		presale_acct: checked_acct(deps.api, Some(accounts.presale_acct)),
+		stake_acct: checked_acct(deps.api, Some(accounts.stake_acct)),
+		unstake_acct: checked_acct(deps.api, Some(accounts.unstake_acct)),
+	})?;
+	Ok(())
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
-deps: DepsMut,
+mut deps: DepsMut,
 env: Env,
 info: MessageInfo,
 msg: InstantiateMsg,
@@ -58,49 +210,98 @@ msg: InstantiateMsg,
 	nonpayable(&info)?;
 	set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 	
-	let creator_addr = String::from(&info.sender);
-	
-	let paramconfig = ParamConfig {
-		yield_percent: 0,
-		burn_percent: 0,
-		social_percent: 0,
-		expense_percent: 0,
-		affiliate_percent: 0,
-		tax_percent: 0,
-		presale_price: 200,
+	// Check everything passed in before anything is stored
+	let paramconfig = match msg.param_config {
+		Some(params) => {
+			check_percent("affiliate_percent", params.affiliate_percent)?;
+			check_percent("tax_percent", params.tax_percent)?;
+			ParamConfig {
+				affiliate_percent: params.affiliate_percent,
+				tax_percent: params.tax_percent,
+				presale_price: params.presale_price,
+			}
+		}
+		None => ParamConfig {
+			affiliate_percent: 0,
+			tax_percent: 0,
+			presale_price: 200,
+		},
 	};
-	// Save the owner address to contract storage.
-	PARAM_CONFIG.save(deps.storage, &paramconfig)?;
 	
+	let accounts = msg.acct_config.unwrap_or(InstantiateAcctConfig {
+		presale_acct: None,
+		stake_acct: None,
+		unstake_acct: None,
+	});
 	let acctconfig = AcctConfig {
-		owner: info.sender,
-		presale_acct: "none".to_string(),
-		yield_acct: "none".to_string(),
-		burn_acct: "none".to_string(),
-		social_acct: "none".to_string(),
-		expense_acct: "none".to_string(),
-		stake_acct: "none".to_string(),
-		unstake_acct: "none".to_string(),
+		owner: Some(info.sender.clone()),
+		presale_acct: accounts.presale_acct.map(|a| deps.api.addr_validate(&a)).transpose()?,
+		stake_acct: accounts.stake_acct.map(|a| deps.api.addr_validate(&a)).transpose()?,
+		unstake_acct: accounts.unstake_acct.map(|a| deps.api.addr_validate(&a)).transpose()?,
 	};
-	// Save the owner address to contract storage.
-	ACCT_CONFIG.save(deps.storage, &acctconfig)?;
 	
-	let dexferconfig = DexferConfig {
-		dexfer_manager: "none".to_string(),
-		deposit_percent: 0,
-		tax_exempt: "none".to_string(),
-		token_minter: "contract".to_string(),
+	// No tax can be charged until the owner sets who receives it
+	let recipients = match msg.tax_recipients {
+		Some(recipients) => checked_tax_recipients(deps.api, recipients)?,
+		None => vec![],
+	};
+	if paramconfig.tax_percent != 0 && recipients.is_empty() {
+		return Err(ContractError::NoTaxRecipients {});
+	}
+	
+	let dexferconfig = match msg.dexfer_config {
+		Some(dexfer) => {
+			check_percent("deposit_percent", dexfer.deposit_percent)?;
+			DexferConfig {
+				dexfer_manager: dexfer.dexfer_manager.map(|a| deps.api.addr_validate(&a)).transpose()?,
+				deposit_percent: dexfer.deposit_percent,
+				tax_exempt: dexfer.tax_exempt.map(|a| deps.api.addr_validate(&a)).transpose()?,
+				token_minter: dexfer.token_minter,
+			}
+		}
+		None => DexferConfig {
+			dexfer_manager: None,
+			deposit_percent: 0,
+			tax_exempt: None,
+			token_minter: "contract".to_string(),
+		},
 	};
-	// Save the owner address to contract storage.
-	DEXFER_CONFIG.save(deps.storage, &dexferconfig)?;
 	
+	// only the creator trades until the owner opens the curve
 	let safetyconfig = SafetyConfig {
-		can_buy: creator_addr.to_string(),
-		can_sell: creator_addr.to_string(),
+		status: match msg.safety_config {
+			Some(status) => checked_status(deps.api, status)?,
+			None => TradingStatus::Allowlist(vec![info.sender.clone()]),
+		},
 	};
+	
+	let marketing = msg.marketing.unwrap_or(InstantiateMarketingInfo {
+		project: None,
+		description: None,
+		marketing: None,
+	});
+	let marketing_addr = match marketing.marketing {
+		Some(addr) => deps.api.addr_validate(&addr)?,
+		None => info.sender.clone(),
+	};
+	
 	// Save the owner address to contract storage.
+	PARAM_CONFIG.save(deps.storage, &paramconfig)?;
+	PARAM_CEILINGS.save(deps.storage, &ParamCeilings::default())?;
+	ACCT_CONFIG.save(deps.storage, &acctconfig)?;
+	TAX_RECIPIENTS.save(deps.storage, &recipients)?;
+	DEXFER_CONFIG.save(deps.storage, &dexferconfig)?;
 	SAFETY_CONFIG.save(deps.storage, &safetyconfig)?;
 	
+	// SyncReserve only reports until the owner configures it
+	RESERVE_CONFIG.save(deps.storage, &ReserveConfig::default())?;
+	
+	// Everything is staked until the owner sets a liquid share
+	LIQUIDITY_CONFIG.save(deps.storage, &LiquidityConfig::default())?;
+	
+	// Fallback chain tax, only used when the chain can't be queried
+	CHAIN_TAX_CONFIG.save(deps.storage, &ChainTaxConfig::default())?;
+	
 	// store token info using cw20-base format
 	let data = TokenInfo {
 		name: msg.name,
@@ -109,28 +310,31 @@ msg: InstantiateMsg,
 		total_supply: Uint128::zero(),
 		// set self as minter, so we can properly execute mint and burn
 		mint: Some(MinterData {
-			minter: env.contract.address,
+			minter: env.contract.address.clone(),
 			cap: None,
 		}),
 	};
 	TOKEN_INFO.save(deps.storage, &data)?;
 	
-	let logo = Logo::Url("".to_owned());
-	LOGO.save(deps.storage, &logo)?;
-	
-	match logo {
-		Logo::Url(url) => Some(LogoInfo::Url(url)),
-		Logo::Embedded(_) => Some(LogoInfo::Embedded),
-	};
+	LOGO.save(deps.storage, &Logo::Url("".to_owned()))?;
 	
 	let metadata = MarketingInfoResponse {
-		project: Some("".to_owned()),
-		description: Some("".to_owned()),
-		marketing: Some(acctconfig.owner),
+		project: Some(marketing.project.unwrap_or_default()),
+		description: Some(marketing.description.unwrap_or_default()),
+		marketing: Some(marketing_addr.clone()),
 		logo: Some(LogoInfo::Url("".to_owned())),
 	};
 	MARKETING_INFO.save(deps.storage, &metadata)?;
 	
+	// cw20-base checks the logo, uploading it as the marketing address
+	if let Some(logo) = msg.logo {
+		let sub_info = MessageInfo {
+			sender: marketing_addr,
+			funds: vec![],
+		};
+		execute_upload_logo(deps.branch(), env, sub_info, logo)?;
+	}
+	
 	let places = DecimalPlaces::new(msg.decimals, msg.reserve_decimals);
 	let supply = CurveState::new(msg.reserve_denom, places);
 	
@@ -165,31 +369,37 @@ info: MessageInfo,
 msg: ExecuteMsg,
 curve_fn: CurveFn,
 ) -> Result<Response, ContractError> {
+	assert_not_paused(deps.storage, &msg)?;
+	
 	match msg {
 		ExecuteMsg::Buy { affiliate } => execute_buy(deps, env, info, curve_fn, affiliate),
 		
 		// we override these from cw20
-		ExecuteMsg::Burn { amount } => Ok(execute_sell(deps, env, info, curve_fn, amount)?),
-		ExecuteMsg::BurnFrom { owner, amount } => {
-			Ok(execute_sell_from(deps, env, info, curve_fn, owner, amount)?)
+		ExecuteMsg::Burn { amount, instant } => {
+			Ok(execute_sell(deps, env, info, curve_fn, amount, instant.unwrap_or(false))?)
+		}
+		ExecuteMsg::BurnFrom { owner, amount, instant } => {
+			Ok(execute_sell_from(deps, env, info, curve_fn, owner, amount, instant.unwrap_or(false))?)
 		}
 		
 		// these all come from cw20-base to implement the cw20 standard
+		// wrapped to enforce the balance cap on the recipient and track its holding period
 		ExecuteMsg::Transfer { recipient, amount } => {
-			Ok(execute_transfer(deps, env, info, recipient, amount)?)
+			execute_capped_transfer(deps, env, info, recipient, amount)
 		}
 		ExecuteMsg::Send {
 			contract,
 			amount,
 			msg,
-		} => Ok(execute_send(deps, env, info, contract, amount, msg)?),
+		} => execute_capped_send(deps, env, info, contract, amount, msg),
 		ExecuteMsg::IncreaseAllowance {
 			spender,
 			amount,
 			expires,
-		} => Ok(execute_increase_allowance(
+		} => execute_checked_increase_allowance(
 		deps, env, info, spender, amount, expires,
-		)?),
+		),
+		// left open so allowances to denylisted spenders can still be revoked
 		ExecuteMsg::DecreaseAllowance {
 			spender,
 			amount,
@@ -201,59 +411,270 @@ curve_fn: CurveFn,
 			owner,
 			recipient,
 			amount,
-		} => Ok(execute_transfer_from(
+		} => execute_capped_transfer_from(
 		deps, env, info, owner, recipient, amount,
-		)?),
+		),
 		ExecuteMsg::SendFrom {
 			owner,
 			contract,
 			amount,
 			msg,
-		} => Ok(execute_send_from(deps, env, info, owner, contract, amount, msg,
-		)?),
+		} => execute_capped_send_from(deps, env, info, owner, contract, amount, msg,
+		),
 		ExecuteMsg::UpdateMarketing {
 			project,
 			description,
 			marketing,
 		} => Ok(execute_update_marketing(deps, env, info, project, description, marketing)?),
 		ExecuteMsg::UploadLogo(logo) => Ok(execute_upload_logo(deps, env, info, logo)?),
-		ExecuteMsg::UpdateParamConfig {  yield_percent, burn_percent, social_percent, expense_percent,
-			affiliate_percent, tax_percent, presale_price,
-		} => Ok(execute_update_paramconfig(deps, env, info, yield_percent, burn_percent, social_percent,
-		expense_percent,affiliate_percent, tax_percent, presale_price,
+		ExecuteMsg::LowerParamCeilings { max_tax_percent, max_affiliate_percent,
+		} => execute_lower_param_ceilings(deps, env, info, max_tax_percent, max_affiliate_percent),
+		ExecuteMsg::ProposeNewOwner { owner, expiry,
+		} => execute_propose_new_owner(deps, env, info, owner, expiry),
+		ExecuteMsg::AcceptOwnership {} => execute_accept_ownership(deps, env, info),
+		ExecuteMsg::CancelOwnershipProposal {} => execute_cancel_ownership_proposal(deps, env, info),
+		ExecuteMsg::RenounceOwnership {} => execute_renounce_ownership(deps, env, info),
+		ExecuteMsg::UpdateRoles { address, roles } => execute_update_roles(deps, env, info, address, roles),
+		// safety knobs apply right away so trading can be reined in without waiting. They only
+		// limit trading, never fees or where funds go, so the timelock doesn't cover them
+		ExecuteMsg::UpdateSafetyConfig { status,
+		} => Ok(execute_update_safetyconfig(deps, env, info, status,
+		)?),
+		ExecuteMsg::UpdateBuyLimits { min_buy, max_buy, address_daily_cap, block_cap,
+		} => Ok(execute_update_buylimits(deps, env, info, min_buy, max_buy, address_daily_cap,
+		block_cap,
+		)?),
+		ExecuteMsg::UpdateWhaleConfig { max_balance_percent, min_supply,
+		} => Ok(execute_update_whaleconfig(deps, env, info, max_balance_percent, min_supply,
+		)?),
+		ExecuteMsg::UpdateCircuitBreaker { max_price_move_percent, max_reserve_move_percent, window,
+			cooldown_seconds,
+		} => Ok(execute_update_circuitbreaker(deps, env, info, max_price_move_percent,
+		max_reserve_move_percent, window, cooldown_seconds,
+		)?),
+		ExecuteMsg::UpdateReserveConfig { tolerance_percent, pause_sells, mark_down,
+		} => Ok(execute_update_reserveconfig(deps, env, info, tolerance_percent, pause_sells,
+		mark_down,
+		)?),
+		ExecuteMsg::UpdateWhaleWhitelist { add, remove,
+		} => execute_update_whale_whitelist(deps, env, info, add, remove),
+		ExecuteMsg::UpdateComplianceDenylist { add, remove,
+		} => execute_update_compliance_denylist(deps, env, info, add, remove),
+		ExecuteMsg::SetPause { action, paused } => execute_set_pause(deps, env, info, action, paused),
+		ExecuteMsg::ResetCircuitBreaker {} => execute_reset_circuit_breaker(deps, env, info),
+		ExecuteMsg::SyncReserve {} => execute_sync_reserve(deps, env, info),
+		ExecuteMsg::DistributeTaxes {} => execute_distribute_taxes(deps, env, info),
+		ExecuteMsg::ReturnSellTax {} => execute_return_sell_tax(deps, env, info),
+		ExecuteMsg::UpdateAffiliateDenylist { add, remove,
+		} => execute_update_affiliate_denylist(deps, env, info, add, remove),
+		ExecuteMsg::RegisterAffiliate { code } => execute_register_affiliate(deps, env, info, code),
+		ExecuteMsg::ClaimAffiliateRewards {} => execute_claim_affiliate_rewards(deps, env, info),
+		// changes to fees and where funds go wait out the timelock
+		msg @ (ExecuteMsg::UpdateParamConfig { .. }
+		| ExecuteMsg::UpdateAcctConfig { .. }
+		| ExecuteMsg::UpdateTaxRecipients { .. }
+		| ExecuteMsg::UpdateDexferConfig { .. }
+		| ExecuteMsg::UpdateMinter { .. }
+		| ExecuteMsg::UpdateLiquidityConfig { .. }
+		| ExecuteMsg::UpdateAffiliateTiers { .. }
+		| ExecuteMsg::UpdateAffiliateRules { .. }
+		| ExecuteMsg::UpdateDistributionConfig { .. }
+		| ExecuteMsg::UpdateChainTaxConfig { .. }
+		| ExecuteMsg::UpdateCooldown { .. }
+		| ExecuteMsg::UpdateHoldingTaxSchedule { .. }
+		| ExecuteMsg::UpdateHolderTiers { .. }
+		| ExecuteMsg::UpdateTimelock { .. }) => queue_change(deps, env, info, msg),
+		ExecuteMsg::ExecutePending { id } => execute_pending(deps, env, info, id),
+		ExecuteMsg::CancelPending { id } => execute_cancel_pending(deps, env, info, id),
+	}
+}
+
+/// Applies a timelocked change, the handler checks the sender as usual
+fn apply_change(
+deps: DepsMut,
+env: Env,
+info: MessageInfo,
+msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+	match msg {
+		ExecuteMsg::UpdateParamConfig { affiliate_percent, tax_percent, presale_price,
+		} => Ok(execute_update_paramconfig(deps, env, info, affiliate_percent, tax_percent, presale_price,
 		)?),
-		ExecuteMsg::UpdateAcctConfig {presale_acct, yield_acct, burn_acct, social_acct,
-			expense_acct, stake_acct, unstake_acct,
-		} => Ok(execute_update_acctconfig(deps, env, info, presale_acct, yield_acct, burn_acct,
-		social_acct, expense_acct, stake_acct, unstake_acct,
+		ExecuteMsg::UpdateAcctConfig {presale_acct, stake_acct, unstake_acct,
+		} => Ok(execute_update_acctconfig(deps, env, info, presale_acct, stake_acct, unstake_acct,
 		)?),
+		ExecuteMsg::UpdateTaxRecipients { recipients,
+		} => execute_update_tax_recipients(deps, env, info, recipients),
 		ExecuteMsg::UpdateDexferConfig { dexfer_manager, deposit_percent, tax_exempt, token_minter,
 		} => Ok(execute_update_dexferconfig(deps, env, info, dexfer_manager, deposit_percent,
 		tax_exempt, token_minter,
 		)?),
-		ExecuteMsg::UpdateSafetyConfig { can_buy, can_sell,
-		} => Ok(execute_update_safetyconfig(deps, env, info, can_buy, can_sell,
-		)?),
 		ExecuteMsg::UpdateMinter { new_minter,
 		} => Ok(execute_update_minter(deps, env, info, new_minter,
 		)?),
+		ExecuteMsg::UpdateLiquidityConfig { liquid_percent, instant_fee_percent,
+		} => Ok(execute_update_liquidityconfig(deps, env, info, liquid_percent, instant_fee_percent,
+		)?),
+		ExecuteMsg::UpdateAffiliateTiers { tiers } => execute_update_affiliate_tiers(deps, env, info, tiers),
+		ExecuteMsg::UpdateAffiliateRules { sticky_referrer,
+		} => Ok(execute_update_affiliaterules(deps, env, info, sticky_referrer,
+		)?),
+		ExecuteMsg::UpdateDistributionConfig { min_interval,
+		} => Ok(execute_update_distributionconfig(deps, env, info, min_interval,
+		)?),
+		ExecuteMsg::UpdateChainTaxConfig { rate, cap,
+		} => Ok(execute_update_chaintaxconfig(deps, env, info, rate, cap,
+		)?),
+		ExecuteMsg::UpdateCooldown { cooldown_seconds, early_exit_tax_percent,
+		} => Ok(execute_update_cooldown(deps, env, info, cooldown_seconds, early_exit_tax_percent,
+		)?),
+		ExecuteMsg::UpdateHoldingTaxSchedule { steps } => execute_update_holding_tax_schedule(deps, env, info, steps),
+		ExecuteMsg::UpdateHolderTiers { tiers } => execute_update_holder_tiers(deps, env, info, tiers),
+		ExecuteMsg::UpdateTimelock { delay,
+		} => Ok(execute_update_timelock(deps, env, info, delay,
+		)?),
+		_ => Err(ContractError::NotTimelocked {}),
 	}
 }
 
-pub fn execute_buy(
+/// Who may queue, apply or cancel a timelocked change
+fn assert_change_auth(storage: &dyn Storage, sender: &Addr, msg: &ExecuteMsg) -> Result<(), ContractError> {
+	match msg {
+		ExecuteMsg::UpdateAcctConfig { .. }
+		| ExecuteMsg::UpdateTaxRecipients { .. }
+		| ExecuteMsg::UpdateDexferConfig { .. } => assert_role(storage, sender, Role::AccountManager),
+		ExecuteMsg::UpdateMinter { .. } => assert_role(storage, sender, Role::MinterAdmin),
+		ExecuteMsg::UpdateTimelock { .. } => assert_owner(storage, sender),
+		_ => assert_role(storage, sender, Role::FeeManager),
+	}
+}
+
+/// Applies right away without a delay, or when the delay is only raised. Otherwise the
+/// change is stored until ExecutePending after the delay
+fn queue_change(
+deps: DepsMut,
+env: Env,
+info: MessageInfo,
+msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+	let delay = TIMELOCK_CONFIG.may_load(deps.storage)?.unwrap_or_default().delay;
+	let raises_delay = matches!(msg, ExecuteMsg::UpdateTimelock { delay: new_delay } if new_delay >= delay);
+	if delay == 0 || raises_delay {
+		return apply_change(deps, env, info, msg);
+	}
+	assert_change_auth(deps.storage, &info.sender, &msg)?;
+	
+	let id = PENDING_CHANGE_ID.may_load(deps.storage)?.unwrap_or_default() + 1;
+	PENDING_CHANGE_ID.save(deps.storage, &id)?;
+	let ready_at = env.block.time.seconds() + delay;
+	PENDING_CHANGES.save(deps.storage, id, &PendingChange {
+		id,
+		msg,
+		proposer: info.sender.clone(),
+		ready_at,
+	})?;
+	
+	Ok(Response::new()
+	.add_attribute("action", "queue_change")
+	.add_attribute("id", id.to_string())
+	.add_attribute("proposer", info.sender)
+	.add_attribute("ready_at", ready_at.to_string()))
+}
+
+pub fn execute_pending(
+deps: DepsMut,
+env: Env,
+info: MessageInfo,
+id: u64,
+) -> Result<Response, ContractError> {
+	let change = PENDING_CHANGES
+	.may_load(deps.storage, id)?
+	.ok_or(ContractError::NoPendingChange { id })?;
+	if env.block.time.seconds() < change.ready_at {
+		return Err(ContractError::ChangeNotReady { id, ready_at: change.ready_at });
+	}
+	PENDING_CHANGES.remove(deps.storage, id);
+	
+	let res = apply_change(deps, env, info, change.msg)?;
+	Ok(res.add_attribute("pending_id", id.to_string()))
+}
+
+pub fn execute_cancel_pending(
+deps: DepsMut,
+_env: Env,
+info: MessageInfo,
+id: u64,
+) -> Result<Response, ContractError> {
+	let change = PENDING_CHANGES
+	.may_load(deps.storage, id)?
+	.ok_or(ContractError::NoPendingChange { id })?;
+	assert_change_auth(deps.storage, &info.sender, &change.msg)?;
+	PENDING_CHANGES.remove(deps.storage, id);
+	
+	Ok(Response::new()
+	.add_attribute("action", "cancel_pending")
+	.add_attribute("id", id.to_string()))
+}
+
+pub fn execute_update_timelock(
 deps: DepsMut,
+_env: Env,
+info: MessageInfo,
+delay: u64,
+) -> Result<Response, ContractError> {
+	
+	//Only owner is authorized to proceed
+	assert_owner(deps.storage, &info.sender)?;
+	
+	TIMELOCK_CONFIG.save(deps.storage, &TimelockConfig { delay })?;
+	
+	Ok(Response::default())
+}
+
+pub fn query_pending_changes(
+deps: Deps,
+start_after: Option<u64>,
+limit: Option<u32>,
+) -> StdResult<PendingChangesResponse> {
+	let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+	let start = start_after.map(Bound::exclusive_int);
+	
+	let changes = PENDING_CHANGES
+	.range(deps.storage, start, None, Order::Ascending)
+	.take(limit)
+	.map(|item| {
+		let (_, change) = item?;
+		Ok(PendingChangeInfo {
+			id: change.id,
+			msg: change.msg,
+			proposer: change.proposer,
+			ready_at: change.ready_at,
+		})
+	})
+	.collect::<StdResult<Vec<_>>>()?;
+	
+	Ok(PendingChangesResponse {
+		delay: TIMELOCK_CONFIG.may_load(deps.storage)?.unwrap_or_default().delay,
+		changes,
+	})
+}
+
+pub fn execute_buy(
+mut deps: DepsMut,
 env: Env,
 info: MessageInfo,
 curve_fn: CurveFn,
 affiliate: String,
 ) -> Result<Response, ContractError> {
 	
-	//Check can_buy flag
+	//Check trading status
 	let check = SAFETY_CONFIG.load(deps.storage)?;
 	
-	if  &check.can_buy != "1" && &check.can_buy != &info.sender.to_string() {
+	if !check.status.can_buy(&info.sender) {
 		return Err(ContractError::MintPaused{});
 	}
+	assert_not_denylisted(deps.storage, "buyer", &info.sender)?;
+	check_circuit_breaker(deps.storage, &env)?;
 	
 	// Load state data
 	let mut state = CURVE_STATE.load(deps.storage)?;
@@ -265,10 +686,14 @@ affiliate: String,
 	
 	let curve = curve_fn(state.clone().decimals);
 	let spot_price = curve.spot_price(state.supply);
+	let reserve_before = state.reserve;
 	let presale_price: Uint128 = Uint128::new(params.presale_price.into());
 	
 	let mut minted: Uint128 = Uint128::new(0);
 	let mut affiliate_amt: Uint128 = Uint128::new(0);
+	let mut affiliate_tier = 0u32;
+	let mut affiliate_code = None;
+	let mut holder_tier = 0u32;
 	
 	//Give error if the presale has ended
 	if  presale_price != Uint128::new(0) && presale_price.u128() < spot_price.u128() {
@@ -276,7 +701,7 @@ affiliate: String,
 	}
 	
 	// fund denom (uluna)
-	let reserve_denom = &state.reserve_denom;
+	let reserve_denom = &state.reserve_denom.clone();
 	
 	// Messages(tx) buffer
 	let mut messages = vec![];
@@ -284,6 +709,12 @@ affiliate: String,
 	//Save gross-in before deductions
 	let gross_in = payment.u128();
 	
+	// the dexfer manager moves liquidity, not a buyer
+	if !special.is_manager(&info.sender) {
+		use_buy_limits(deps.storage, &env, &info.sender, payment)?;
+		LAST_BUY.save(deps.storage, &info.sender, &env.block.time.seconds())?;
+	}
+	
 	// Calc tax
 	let tax_full_amt: Uint128;
 	let mut net_payment_amt: Uint128;
@@ -291,69 +722,33 @@ affiliate: String,
 
 	let mut deposit_amt = Uint128::new(0u128);
 	let mut returned_amt = Uint128::new(0u128);
+	let mut liquid_amt = Uint128::new(0u128);
+	let mut staked_amt = Uint128::new(0u128);
 
-	//Subtract mandatory chain_burn_tax (for buy&sell together since not paid on burn)
-	payment = payment.clone()
-		.checked_mul(Uint128::new(995)).unwrap()
-		.checked_div(Uint128::new(1000)).unwrap();
+	// chain burn tax is charged on each send below, the amounts sent are net of it
+	let chain_tax = load_chain_tax(deps.as_ref(), reserve_denom)?.0;
+	let liquidity = LIQUIDITY_CONFIG.may_load(deps.storage)?.unwrap_or_default();
 	
 	// Don't charge BASE Tax in some special cases
-	if  presale_price == Uint128::new(0) && &special.dexfer_manager != &info.sender.to_string() && &special.tax_exempt != &info.sender.to_string() {
+	if  presale_price == Uint128::new(0) && !special.is_manager(&info.sender) && !special.is_tax_exempt(&info.sender) {
+		
+		// discount by the BASE held before this buy
+		let (tier, tax_percent) = holder_tax_percent(deps.storage, &info.sender, params.tax_percent)?;
+		holder_tier = tier;
 		
 		tax_full_amt = payment
-		.checked_mul(Uint128::new(params.tax_percent.into())).unwrap()
+		.checked_mul(Uint128::new(tax_percent.into())).unwrap()
 		.checked_div(Uint128::new(1000)).unwrap();
 		
 		//This is the amount left after the total tax is collected
 		net_payment_amt = payment - tax_full_amt;
 		
-		// tax breakdown
-		let tax_yield_amt = tax_full_amt
-		.checked_mul(Uint128::new(params.yield_percent.into())).unwrap()
-		.checked_div(Uint128::new(1000)).unwrap();
-		
-		let tax_burn_amt = tax_full_amt
-		.checked_mul(Uint128::new(params.burn_percent.into())).unwrap()
-		.checked_div(Uint128::new(1000)).unwrap();
-		
-		let tax_social_amt = tax_full_amt
-		.checked_mul(Uint128::new(params.social_percent.into())).unwrap()
-		.checked_div(Uint128::new(1000)).unwrap();
-		
-		let tax_expense_amt = tax_full_amt
-		.checked_sub(tax_yield_amt).unwrap()
-		.checked_sub(tax_burn_amt).unwrap()
-		.checked_sub(tax_social_amt).unwrap();
-		
-		// tax deposit addresses
-		let tax_yield_addr  = deps.api.addr_validate(&accounts.yield_acct)?;
-		let tax_burn_addr   = deps.api.addr_validate(&accounts.burn_acct)?;
-		let tax_social_addr = deps.api.addr_validate(&accounts.social_acct)?;
-		let tax_expen_addr  = deps.api.addr_validate(&accounts.expense_acct)?;
-		
-		//Update lifetime tax collected
+		//Update lifetime tax collected, it stays on the contract until DistributeTaxes
 		state.tax_collected += Uint128::from(tax_full_amt);
 		
-		// Build messages(tx) to send
-		messages.push(CosmosMsg::Bank(BankMsg::Send {
-			to_address: tax_yield_addr.to_string(),
-			amount: coins(tax_yield_amt.u128(), reserve_denom),
-		}));
-		
-		messages.push(CosmosMsg::Bank(BankMsg::Send {
-			to_address: tax_burn_addr.to_string(),
-			amount: coins(tax_burn_amt.u128(), reserve_denom),
-		}));
-		
-		messages.push(CosmosMsg::Bank(BankMsg::Send {
-			to_address: tax_social_addr.to_string(),
-			amount: coins(tax_social_amt.u128(), reserve_denom),
-		}));
-		
-		messages.push(CosmosMsg::Bank(BankMsg::Send {
-			to_address: tax_expen_addr.to_string(),
-			amount: coins(tax_expense_amt.u128(), reserve_denom),
-		}));
+		let recipients = TAX_RECIPIENTS.may_load(deps.storage)?.unwrap_or_default();
+		let shares = split_tax(tax_full_amt, &recipients)?;
+		accrue_tax(deps.storage, &TAX_PENDING, &recipients, &shares)?;
 		
 		payment = Uint128::from(net_payment_amt);
 		
@@ -362,36 +757,54 @@ affiliate: String,
 		tax_full_amt = Uint128::zero();
 	}
 	
-	//Msg for affiliate reward
-	if  affiliate != "" && &special.dexfer_manager != &info.sender.to_string() && &special.tax_exempt != &info.sender.to_string(){
+	let referral = if !special.is_manager(&info.sender) && !special.is_tax_exempt(&info.sender) {
+		resolve_affiliate(deps.branch(), &info.sender, &affiliate)?
+	} else {
+		None
+	};
+	
+	//Accrue affiliate reward, the affiliate claims it with ClaimAffiliateRewards
+	if let Some((code, mut referrer)) = referral {
 		
-		let affiliate_addr = deps.api.addr_validate(&affiliate)?;
+		// tier from the volume before this buy
+		let tiers = AFFILIATE_TIERS.may_load(deps.storage)?.unwrap_or_default();
+		let (tier, affiliate_percent) = tier_for(params.affiliate_percent, &tiers, referrer.volume);
+		affiliate_tier = tier;
 		
 		affiliate_amt = payment
-		.checked_mul(Uint128::new(params.affiliate_percent.into())).unwrap()
+		.checked_mul(Uint128::new(affiliate_percent.into())).unwrap()
 		.checked_div(Uint128::new(1000)).unwrap();
 		
 		net_payment_amt = payment.checked_sub(affiliate_amt).unwrap();
 		
-		messages.push(CosmosMsg::Bank(BankMsg::Send {
-			to_address: affiliate_addr.to_string(),
-			amount: coins(affiliate_amt.u128(), reserve_denom),
-		}));
+		referrer.volume += Uint128::from(gross_in);
+		referrer.referrals += 1;
+		referrer.rewards_earned += affiliate_amt;
+		AFFILIATES.save(deps.storage, &code, &referrer)?;
+		state.affiliate_owed += affiliate_amt;
+		affiliate_code = Some(code);
 		
 		payment = net_payment_amt;
 	}
 	
 	// calculate how many tokens can be purchased with this
 	// rides curve if presale_price == 0, else uses presale_price
-	if  presale_price == Uint128::new(0) || &special.dexfer_manager == &info.sender.to_string(){
+	if  presale_price == Uint128::new(0) || special.is_manager(&info.sender){
+		if  !special.is_manager(&info.sender) {
+			// the reserve only grows by what is kept liquid and what the stake account receives
+			liquid_amt = liquid_top_up(&state, &liquidity, payment);
+			staked_amt = chain_tax.deduct(payment - liquid_amt);
+			payment = liquid_amt + staked_amt;
+		}
+		
+		// price off the curve as if nothing was slashed, the written down part stays shared by holders
+		let new_supply = curve.supply(state.reserve + state.slashed_reserve + payment);
 		state.reserve += payment;
-		let new_supply = curve.supply(state.reserve);
+		state.liquid_reserve += liquid_amt;
 		minted = new_supply
 		.checked_sub(state.supply)
 		.map_err(StdError::overflow)?;
 		state.supply = new_supply;
-		
-		CURVE_STATE.save(deps.storage, &state)?;
 	}
 	else {
 		// Calc expected supply
@@ -407,29 +820,31 @@ affiliate: String,
 		let before_reserve = curve.reserve(state.supply);
 		let after_reserve = curve.reserve(state.supply + minted);
 		let delta_reserve = after_reserve - before_reserve;
-		presale_fund = payment.clone().checked_sub(delta_reserve).unwrap();
+		
+		// the pre-sale fund covers the chain tax on the stake send
+		liquid_amt = liquid_top_up(&state, &liquidity, delta_reserve);
+		staked_amt = delta_reserve - liquid_amt;
+		presale_fund = payment
+		.checked_sub(liquid_amt + staked_amt + chain_tax.tax_on(staked_amt))
+		.map_err(|_| ContractError::TooLittle{})?;
 		
 		// Update State Variable
 		state.reserve += delta_reserve;
+		state.liquid_reserve += liquid_amt;
 		state.supply += minted;
 		
-		CURVE_STATE.save(deps.storage, &state)?;
-		
 		// Build message for sending to pre-sale fund
-		let presale_addr  = deps.api.addr_validate(&accounts.presale_acct)?;
-		messages.push(CosmosMsg::Bank(BankMsg::Send {
-			to_address: presale_addr.to_string(),
-			amount: coins(presale_fund.u128(), reserve_denom),
-		}));
+		let presale_addr = required_acct(&accounts.presale_acct, "presale_acct")?;
+		messages.push(bank_send(&presale_addr, presale_fund, reserve_denom, &chain_tax));
 		
 		payment = delta_reserve;
 		
 	}
 	
 	// Refund all but deposit to dexfer_manager to fund DEX
-	if  &special.dexfer_manager == &info.sender.to_string()  {
+	if  special.is_manager(&info.sender)  {
 		
-		let dexfer_addr = deps.api.addr_validate(&special.dexfer_manager)?;
+		let dexfer_addr = required_acct(&special.dexfer_manager, "dexfer_manager")?;
 		
 		deposit_amt = payment.clone()
 		.checked_mul(Uint128::new(special.deposit_percent.into())).unwrap()
@@ -438,20 +853,29 @@ affiliate: String,
 		returned_amt = payment.clone()
 		.checked_sub(deposit_amt).unwrap();
 		
-		messages.push(CosmosMsg::Bank(BankMsg::Send {
-			to_address: dexfer_addr.to_string(),
-			amount: coins(returned_amt.clone().u128(), reserve_denom),
-		}));
+		messages.push(bank_send(&dexfer_addr, returned_amt, reserve_denom, &chain_tax));
 	}
 	else{
 		
-        //send amount left to stake account
-        let stake_addr = deps.api.addr_validate(&accounts.stake_acct)?;
-        messages.push(CosmosMsg::Bank(BankMsg::Send {
-			to_address: stake_addr.to_string(),
-			amount: coins(payment.clone().u128(), reserve_denom),
-		}));
+        //send amount not kept liquid to stake account, its chain tax is already set aside
+        if !staked_amt.is_zero() {
+			let stake_addr = required_acct(&accounts.stake_acct, "stake_acct")?;
+			messages.push(CosmosMsg::Bank(BankMsg::Send {
+				to_address: stake_addr.to_string(),
+				amount: coins(staked_amt.u128(), reserve_denom),
+			}));
+		}
 	}
+	
+	let breaker_trip = record_trade(
+		deps.storage,
+		&env,
+		(spot_price, reserve_before),
+		curve.spot_price(state.supply),
+		state.reserve,
+	)?;
+	CURVE_STATE.save(deps.storage, &state)?;
+	
 	// call into cw20-base to mint the token, call as self as no one else is allowed
 	let sender_addr = env.contract.address.clone();
 	if &special.token_minter != "contract" {
@@ -463,7 +887,9 @@ affiliate: String,
 		sender: sender_addr,
 		funds: vec![],
 	};
-	execute_mint(deps, env, sub_info, info.sender.to_string(), minted.clone())?;
+	record_acquisition(deps.storage, &env, &info.sender, minted)?;
+	execute_mint(deps.branch(), env.clone(), sub_info, info.sender.to_string(), minted)?;
+	assert_max_balance(deps.storage, &info.sender)?;
 	
 	//Send Transactions
 	let mut res = Response::new()
@@ -473,24 +899,31 @@ affiliate: String,
 	
 	res = res.add_attribute("LUNC Sent: ", Uint128::from(gross_in));
 	res = res.add_attribute("LUNC Tax: ", Uint128::from(tax_full_amt));
+	res = res.add_attribute("Holder Tier: ", holder_tier.to_string());
 	
-	if  affiliate != "" && &special.dexfer_manager != &info.sender.to_string() && &special.tax_exempt != &info.sender.to_string() {
+	if let Some(code) = affiliate_code {
+		res = res.add_attribute("Affiliate: ", code);
 		res = res.add_attribute("Affiliate Reward: ", Uint128::from(affiliate_amt));
+		res = res.add_attribute("Affiliate Tier: ", affiliate_tier.to_string());
 	}
 
-	if &special.dexfer_manager == &info.sender.to_string(){
+	if special.is_manager(&info.sender){
 		res = res.add_attribute("LUNC Deposit: ", Uint128::from(deposit_amt));
 		res = res.add_attribute("Xfer to DEX: ", Uint128::from(returned_amt));
 	}
 	else{
-	res = res.add_attribute("LUNC Staked: ", Uint128::from(payment));
+	res = res.add_attribute("LUNC Staked: ", staked_amt);
+	res = res.add_attribute("LUNC Kept Liquid: ", liquid_amt);
 	}
 
-	if  presale_price != Uint128::new(0) && &special.dexfer_manager != &info.sender.to_string(){
+	if  presale_price != Uint128::new(0) && !special.is_manager(&info.sender){
 		res = res.add_attribute("Pre-sale Fund: ", Uint128::from(presale_fund));
 	}
 
 	res = res.add_attribute("BASE Minted: ", Uint128::from(minted));
+	if let Some(reason) = breaker_trip {
+		res = res.add_attribute("Circuit Breaker: ", reason);
+	}
 	
 	Ok(res)
 }
@@ -501,19 +934,20 @@ env: Env,
 info: MessageInfo,
 curve_fn: CurveFn,
 amount: Uint128,
+instant: bool,
 ) -> Result<Response, ContractError> {
 	
-	//Check can_sell flag
+	//Check trading status
 	let check = SAFETY_CONFIG.load(deps.storage)?;
 	
-	if  &check.can_sell != "1" && &check.can_sell != &info.sender.to_string() {
+	if !check.status.can_sell(&info.sender) {
 		return Err(ContractError::BurnPaused{});
 	}
 	
 	nonpayable(&info)?;
 	let receiver = info.sender.clone();
 	// do all the work
-	let mut res = do_sell(deps, env, info, curve_fn, receiver, amount)?;
+	let mut res = do_sell(deps, env, info, curve_fn, receiver, amount, instant)?;
 	
 	// add our custom attributes
 	res.attributes.push(attr("action", "burn"));
@@ -527,9 +961,18 @@ info: MessageInfo,
 curve_fn: CurveFn,
 owner: String,
 amount: Uint128,
+instant: bool,
 ) -> Result<Response, ContractError> {
-	nonpayable(&info)?;
 	let owner_addr = deps.api.addr_validate(&owner)?;
+	
+	//Check trading status, it is the owner's tokens being sold
+	let check = SAFETY_CONFIG.load(deps.storage)?;
+	
+	if !check.status.can_sell(&owner_addr) {
+		return Err(ContractError::BurnPaused{});
+	}
+	
+	nonpayable(&info)?;
 	let spender_addr = info.sender.clone();
 	
 	// deduct allowance before doing anything else have enough allowance
@@ -548,6 +991,7 @@ amount: Uint128,
 	curve_fn,
 	receiver_addr.clone(),
 	amount,
+	instant,
 	)?;
 	
 	// add our custom attributes
@@ -556,163 +1000,452 @@ amount: Uint128,
 	Ok(res)
 }
 
-fn do_sell(
+pub fn execute_capped_transfer(
 mut deps: DepsMut,
 env: Env,
-// info.sender is the one burning tokens
 info: MessageInfo,
-curve_fn: CurveFn,
-// receiver is the one who gains (same for execute_sell, diff for execute_sell_from)
-_receiver: Addr,
+recipient: String,
 amount: Uint128,
 ) -> Result<Response, ContractError> {
-	// burn from the caller, this ensures there are tokens to cover this
-	execute_burn(deps.branch(), env, info.clone(), amount)?;
-	
-	// Load state data
-	let accounts = ACCT_CONFIG.load(deps.storage)?;
-	let params = PARAM_CONFIG.load(deps.storage)?;
-	let special = DEXFER_CONFIG.load(deps.storage)?;
-	let mut state = CURVE_STATE.load(deps.storage)?;
-	
-	// calculate how many tokens to release
-	let curve = curve_fn(state.clone().decimals);
-	state.supply = state
-	.supply
-	.checked_sub(amount)
-	.map_err(StdError::overflow)?;
-	let new_reserve = curve.reserve(state.supply);
-	let released = state
-	.reserve
-	.checked_sub(new_reserve)
-	.map_err(StdError::overflow)?;
-	state.reserve = new_reserve;
-	
-	// fund denom (uluna)
-	let reserve_denom = state.reserve_denom.clone();
-	// Messages(tx) buffer
-	let mut messages = vec![];
-	let mut post_tax_amt: Uint128 = Uint128::new(0);
-	let mut net_released_amt: Uint128 = Uint128::new(0);
-	
-	if  &special.tax_exempt != &info.sender.to_string() && &special.dexfer_manager != &info.sender.to_string() {
-		// Calc tax
-		post_tax_amt = released
-		.checked_mul(Uint128::new(params.tax_percent.into())).unwrap()
-		.checked_div(Uint128::new(1000)).unwrap();
-		
-		//This is the amount left after the total tax is collected
-		net_released_amt = released
-		.checked_sub(post_tax_amt).unwrap();
-		
-		// tax breakdown
-		let tax_yield_amt = post_tax_amt
-		.checked_mul(Uint128::new(params.yield_percent.into())).unwrap()
-		.checked_div(Uint128::new(1000)).unwrap();
-		
-		let tax_burn_amt = post_tax_amt
-		.checked_mul(Uint128::new(params.burn_percent.into())).unwrap()
-		.checked_div(Uint128::new(1000)).unwrap();
-		
-		let tax_social_amt = post_tax_amt
-		.checked_mul(Uint128::new(params.social_percent.into())).unwrap()
-		.checked_div(Uint128::new(1000)).unwrap();
-		
-		let _tax_expense_amt = post_tax_amt
-		.checked_sub(tax_yield_amt).unwrap()
-		.checked_sub(tax_burn_amt).unwrap()
-		.checked_sub(tax_social_amt).unwrap();
-				
-		//Update lifetime tax collected
-		state.tax_collected += Uint128::from(post_tax_amt.clone());
-		
-		// tax deposit addresses
-		let _tax_yield_addr  = deps.api.addr_validate(&accounts.yield_acct)?;
-		let _tax_burn_addr   = deps.api.addr_validate(&accounts.burn_acct)?;
-		let _tax_social_addr = deps.api.addr_validate(&accounts.social_acct)?;
-		let _tax_expense_addr  = deps.api.addr_validate(&accounts.expense_acct)?;
-		
-			/*Do Not actually build messages, as funds are in validator
-			
-			// Build tx for tax collection
-			messages.push(CosmosMsg::Bank(BankMsg::Send {
-			to_address: tax_yield_addr.to_string(),
-			amount: coins(tax_yield_amt.u128(), &reserve_denom),
-			}));
-			
-			messages.push(CosmosMsg::Bank(BankMsg::Send {
-			to_address: tax_burn_addr.to_string(),
-			amount: coins(tax_burn_amt.u128(), &reserve_denom),
-			}));
-			
-			messages.push(CosmosMsg::Bank(BankMsg::Send {
-			to_address: tax_social_addr.to_string(),
-			amount: coins(tax_social_amt.u128(), &reserve_denom),
-			}));
-			
-			messages.push(CosmosMsg::Bank(BankMsg::Send {
-			to_address: tax_expense_addr.to_string(),
-			amount: coins(tax_expense_amt.u128(), &reserve_denom),
-		})); */
-		
-		} else {
-		//tax excempt
-		net_released_amt = released.clone();
+	let recipient_addr = deps.api.addr_validate(&recipient)?;
+	assert_not_denylisted(deps.storage, "sender", &info.sender)?;
+	assert_not_denylisted(deps.storage, "recipient", &recipient_addr)?;
+	if recipient_addr != info.sender {
+		record_acquisition(deps.storage, &env, &recipient_addr, amount)?;
+		carry_last_buy(deps.storage, &info.sender, &recipient_addr)?;
 	}
-	
-	// No uluna is returned here since all funds are in the validator
-	// These messages are for documentation only
-	net_released_amt = Uint128::zero();
-	
-	if &special.dexfer_manager != &info.sender.to_string() {
-		
-		let unstake_addr = deps.api.addr_validate(&accounts.unstake_acct)?;
-		// Build messages(tx) to send transfer 101uluna to track this tx
-		messages.push(CosmosMsg::Bank(BankMsg::Send {
-			to_address: unstake_addr.to_string(),
-			amount: coins(101u128, &reserve_denom),
-		}));
-    	}
-	else{
+	let res = execute_transfer(deps.branch(), env, info, recipient, amount)?;
+	assert_max_balance(deps.storage, &recipient_addr)?;
+	Ok(res)
+}
 
-        let dexfer_addr = deps.api.addr_validate(&special.dexfer_manager)?;
-        // Build messages(tx) to send transfer 102uluna to track this tx
-        messages.push(CosmosMsg::Bank(BankMsg::Send {
-			to_address: dexfer_addr.to_string(),
-			amount: coins(102u128, &reserve_denom),
-		}));
-        		
+pub fn execute_capped_send(
+mut deps: DepsMut,
+env: Env,
+info: MessageInfo,
+contract: String,
+amount: Uint128,
+msg: Binary,
+) -> Result<Response, ContractError> {
+	let contract_addr = deps.api.addr_validate(&contract)?;
+	assert_not_denylisted(deps.storage, "sender", &info.sender)?;
+	assert_not_denylisted(deps.storage, "recipient", &contract_addr)?;
+	if contract_addr != info.sender {
+		record_acquisition(deps.storage, &env, &contract_addr, amount)?;
+		carry_last_buy(deps.storage, &info.sender, &contract_addr)?;
 	}
-
-	// Save the state
-	CURVE_STATE.save(deps.storage, &state)?;
-	
-	// Send Transactions
-	let res = Response::new()
-	.add_messages(messages)
-	.add_attribute("from", info.sender)
-	.add_attribute("BASE Burn: ", amount)
-	.add_attribute("LUNC Unstake: ", released)
-	.add_attribute("LUNC Tax: ", post_tax_amt)
-	.add_attribute("Net Unstake: ", net_released_amt)
-	.add_attribute("Unstake Period: ", "21 Days");
-	
+	let res = execute_send(deps.branch(), env, info, contract, amount, msg)?;
+	assert_max_balance(deps.storage, &contract_addr)?;
 	Ok(res)
 }
 
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
-	// default implementation stores curve info as enum, you can do something else in a derived
-	// contract and just pass in your custom curve to do_execute
-	let curve_type = CURVE_TYPE.load(deps.storage)?;
-	let curve_fn = curve_type.to_curve_fn();
-	do_query(deps, env, msg, curve_fn)
+pub fn execute_capped_transfer_from(
+mut deps: DepsMut,
+env: Env,
+info: MessageInfo,
+owner: String,
+recipient: String,
+amount: Uint128,
+) -> Result<Response, ContractError> {
+	let recipient_addr = deps.api.addr_validate(&recipient)?;
+	assert_not_denylisted(deps.storage, "spender", &info.sender)?;
+	let owner_addr = deps.api.addr_validate(&owner)?;
+	assert_not_denylisted(deps.storage, "owner", &owner_addr)?;
+	assert_not_denylisted(deps.storage, "recipient", &recipient_addr)?;
+	if recipient_addr != owner_addr {
+		record_acquisition(deps.storage, &env, &recipient_addr, amount)?;
+		carry_last_buy(deps.storage, &owner_addr, &recipient_addr)?;
+	}
+	let res = execute_transfer_from(deps.branch(), env, info, owner, recipient, amount)?;
+	assert_max_balance(deps.storage, &recipient_addr)?;
+	Ok(res)
+}
+
+pub fn execute_capped_send_from(
+mut deps: DepsMut,
+env: Env,
+info: MessageInfo,
+owner: String,
+contract: String,
+amount: Uint128,
+msg: Binary,
+) -> Result<Response, ContractError> {
+	let contract_addr = deps.api.addr_validate(&contract)?;
+	assert_not_denylisted(deps.storage, "spender", &info.sender)?;
+	let owner_addr = deps.api.addr_validate(&owner)?;
+	assert_not_denylisted(deps.storage, "owner", &owner_addr)?;
+	assert_not_denylisted(deps.storage, "recipient", &contract_addr)?;
+	if contract_addr != owner_addr {
+		record_acquisition(deps.storage, &env, &contract_addr, amount)?;
+		carry_last_buy(deps.storage, &owner_addr, &contract_addr)?;
+	}
+	let res = execute_send_from(deps.branch(), env, info, owner, contract, amount, msg)?;
+	assert_max_balance(deps.storage, &contract_addr)?;
+	Ok(res)
+}
+
+pub fn execute_checked_increase_allowance(
+deps: DepsMut,
+env: Env,
+info: MessageInfo,
+spender: String,
+amount: Uint128,
+expires: Option<Expiration>,
+) -> Result<Response, ContractError> {
+	assert_not_denylisted(deps.storage, "owner", &info.sender)?;
+	assert_not_denylisted(deps.storage, "spender", &deps.api.addr_validate(&spender)?)?;
+	Ok(execute_increase_allowance(deps, env, info, spender, amount, expires)?)
+}
+
+/// Refuses addr when it is on the compliance denylist, rule naming the part it plays
+fn assert_not_denylisted(storage: &dyn Storage, rule: &str, addr: &Addr) -> Result<(), ContractError> {
+	if let Some(reason) = COMPLIANCE_DENYLIST.may_load(storage, addr)? {
+		return Err(ContractError::Denylisted {
+			rule: rule.to_string(),
+			address: addr.to_string(),
+			reason,
+		});
+	}
+	Ok(())
+}
+
+pub fn execute_update_compliance_denylist(
+deps: DepsMut,
+_env: Env,
+info: MessageInfo,
+add: Vec<DenylistEntry>,
+remove: Vec<String>,
+) -> Result<Response, ContractError> {
+	
+	//Only owner is authorized to proceed
+	assert_owner(deps.storage, &info.sender)?;
+	
+	for entry in add.iter() {
+		COMPLIANCE_DENYLIST.save(deps.storage, &deps.api.addr_validate(&entry.address)?, &entry.reason)?;
+	}
+	for addr in remove.iter() {
+		COMPLIANCE_DENYLIST.remove(deps.storage, &deps.api.addr_validate(addr)?);
+	}
+	
+	Ok(Response::new()
+	.add_attribute("action", "update_compliance_denylist")
+	.add_attribute("added", add.len().to_string())
+	.add_attribute("removed", remove.len().to_string()))
+}
+
+pub fn query_compliance_denylist(
+deps: Deps,
+start_after: Option<String>,
+limit: Option<u32>,
+) -> StdResult<ComplianceDenylistResponse> {
+	let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+	let start = start_after.map(Bound::exclusive);
+	
+	let entries = COMPLIANCE_DENYLIST
+	.range(deps.storage, start, None, Order::Ascending)
+	.take(limit)
+	.map(|item| {
+		let (addr, reason) = item?;
+		Ok(DenylistEntryInfo {
+			address: Addr::unchecked(String::from_utf8(addr)?),
+			reason,
+		})
+	})
+	.collect::<StdResult<Vec<_>>>()?;
+	
+	Ok(ComplianceDenylistResponse { entries })
+}
+
+/// Refuses to leave holder with more than max_balance_percent of supply, unless whitelisted
+fn assert_max_balance(storage: &dyn Storage, holder: &Addr) -> Result<(), ContractError> {
+	let max = match max_balance(storage)? {
+		Some(max) => max,
+		None => return Ok(()),
+	};
+	if WHALE_WHITELIST.has(storage, holder) {
+		return Ok(());
+	}
+	let balance = BALANCES.may_load(storage, holder)?.unwrap_or_default();
+	if balance > max {
+		return Err(ContractError::MaxBalanceExceeded {
+			holder: holder.to_string(),
+			max,
+		});
+	}
+	Ok(())
+}
+
+/// Balance cap at the current supply, None when there is no cap
+fn max_balance(storage: &dyn Storage) -> StdResult<Option<Uint128>> {
+	let config = WHALE_CONFIG.may_load(storage)?.unwrap_or_default();
+	if config.max_balance_percent == 0 {
+		return Ok(None);
+	}
+	let supply = TOKEN_INFO.load(storage)?.total_supply;
+	// a share of a small supply would shut out the first buyers
+	if supply < config.min_supply {
+		return Ok(None);
+	}
+	Ok(Some(supply.multiply_ratio(config.max_balance_percent, 1000u128)))
+}
+
+pub fn query_whale_info(
+deps: Deps,
+start_after: Option<String>,
+limit: Option<u32>,
+) -> StdResult<WhaleInfoResponse> {
+	let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+	let start = start_after.map(Bound::exclusive);
+	
+	let whitelist = WHALE_WHITELIST
+	.keys(deps.storage, start, None, Order::Ascending)
+	.take(limit)
+	.map(|addr| Ok(Addr::unchecked(String::from_utf8(addr)?)))
+	.collect::<StdResult<Vec<_>>>()?;
+	
+	let config = WHALE_CONFIG.may_load(deps.storage)?.unwrap_or_default();
+	Ok(WhaleInfoResponse {
+		max_balance_percent: config.max_balance_percent,
+		min_supply: config.min_supply,
+		max_balance: max_balance(deps.storage)?,
+		whitelist,
+	})
+}
+
+pub fn execute_update_whaleconfig(
+deps: DepsMut,
+_env: Env,
+info: MessageInfo,
+max_balance_percent: u32,
+min_supply: Uint128,
+) -> Result<Response, ContractError> {
+	
+	//Only owner or Pauser is authorized to proceed
+	assert_role(deps.storage, &info.sender, Role::Pauser)?;
+	
+	check_percent("max_balance_percent", max_balance_percent)?;
+	
+	// Save config back to contract storage.
+	WHALE_CONFIG.save(deps.storage, &WhaleConfig { max_balance_percent, min_supply })?;
+	
+	Ok(Response::default())
+}
+
+pub fn execute_update_whale_whitelist(
+deps: DepsMut,
+_env: Env,
+info: MessageInfo,
+add: Vec<String>,
+remove: Vec<String>,
+) -> Result<Response, ContractError> {
+	
+	//Only owner or Pauser is authorized to proceed
+	assert_role(deps.storage, &info.sender, Role::Pauser)?;
+	
+	for addr in add.iter() {
+		WHALE_WHITELIST.save(deps.storage, &deps.api.addr_validate(addr)?, &true)?;
+	}
+	for addr in remove.iter() {
+		WHALE_WHITELIST.remove(deps.storage, &deps.api.addr_validate(addr)?);
+	}
+	
+	Ok(Response::new()
+	.add_attribute("action", "update_whale_whitelist")
+	.add_attribute("added", add.len().to_string())
+	.add_attribute("removed", remove.len().to_string()))
+}
+
+fn do_sell(
+mut deps: DepsMut,
+env: Env,
+// info.sender is the one burning tokens
+info: MessageInfo,
+curve_fn: CurveFn,
+// receiver is the one who gains (same for execute_sell, diff for execute_sell_from)
+receiver: Addr,
+amount: Uint128,
+// pay out of the liquid reserve for a fee instead of unbonding
+instant: bool,
+) -> Result<Response, ContractError> {
+	assert_not_denylisted(deps.storage, "seller", &info.sender)?;
+	assert_not_denylisted(deps.storage, "receiver", &receiver)?;
+	check_circuit_breaker(deps.storage, &env)?;
+	
+	// burn from the caller, this ensures there are tokens to cover this
+	execute_burn(deps.branch(), env.clone(), info.clone(), amount)?;
+	
+	// Load state data
+	let accounts = ACCT_CONFIG.load(deps.storage)?;
+	let params = PARAM_CONFIG.load(deps.storage)?;
+	let special = DEXFER_CONFIG.load(deps.storage)?;
+	let mut state = CURVE_STATE.load(deps.storage)?;
+	
+	// calculate how many tokens to release
+	let curve = curve_fn(state.clone().decimals);
+	let before = (curve.spot_price(state.supply), state.reserve);
+	state.supply = state
+	.supply
+	.checked_sub(amount)
+	.map_err(StdError::overflow)?;
+	let new_reserve = curve.reserve(state.supply);
+	let curve_reserve = state.reserve + state.slashed_reserve;
+	let mut released = curve_reserve
+	.checked_sub(new_reserve)
+	.map_err(StdError::overflow)?;
+	
+	// After a slashing every seller takes the same haircut on what the curve releases
+	if !state.slashed_reserve.is_zero() {
+		let full_release = released;
+		released = full_release.multiply_ratio(state.reserve, curve_reserve);
+		state.slashed_reserve = state.slashed_reserve
+		.checked_sub(full_release - released)
+		.unwrap_or_else(|_| Uint128::zero());
+	}
+	state.reserve = state
+	.reserve
+	.checked_sub(released)
+	.map_err(StdError::overflow)?;
+	
+	// fund denom (uluna)
+	let reserve_denom = state.reserve_denom.clone();
+	// Messages(tx) buffer
+	let mut messages = vec![];
+	let mut post_tax_amt: Uint128 = Uint128::new(0);
+	let mut net_released_amt: Uint128 = Uint128::new(0);
+	let recipients = TAX_RECIPIENTS.may_load(deps.storage)?.unwrap_or_default();
+	let mut sell_tax = vec![];
+	let mut early_exit = false;
+	let mut tax_percent = 0u32;
+	
+	if  !special.is_tax_exempt(&info.sender) && !special.is_manager(&info.sender) {
+		tax_percent = holding_tax_percent(deps.storage, &env, &info.sender, params.tax_percent)?;
+		if let Some(ends_at) = cooldown_ends(deps.storage, &env, &info.sender)? {
+			let cooldown = COOLDOWN_CONFIG.load(deps.storage)?;
+			match cooldown.early_exit_tax_percent {
+				Some(early_exit_percent) => tax_percent = std::cmp::max(tax_percent, early_exit_percent),
+				None => return Err(ContractError::SellCooldown { ends_at }),
+			}
+			early_exit = true;
+		}
+		
+		// Calc tax
+		post_tax_amt = released
+		.checked_mul(Uint128::new(tax_percent.into())).unwrap()
+		.checked_div(Uint128::new(1000)).unwrap();
+		
+		//This is the amount left after the total tax is collected
+		net_released_amt = released
+		.checked_sub(post_tax_amt).unwrap();
+		
+		sell_tax = split_tax(post_tax_amt, &recipients)?;
+		
+		//Update lifetime tax collected
+		state.tax_collected += Uint128::from(post_tax_amt.clone());
+		
+		} else {
+		//tax excempt
+		net_released_amt = released.clone();
+	}
+	
+	// Instant sells are paid from the liquid reserve, the fee stays behind in the reserve
+	let mut instant_fee = Uint128::zero();
+	let mut instant_paid = false;
+	if instant && !special.is_manager(&info.sender) {
+		let liquidity = LIQUIDITY_CONFIG.may_load(deps.storage)?.unwrap_or_default();
+		instant_fee = net_released_amt.multiply_ratio(liquidity.instant_fee_percent, 1000u128);
+		
+		// tax leaves the reserve along with the payout
+		let from_liquid = released - instant_fee;
+		if !net_released_amt.is_zero() && from_liquid <= state.liquid_reserve {
+			net_released_amt -= instant_fee;
+			state.liquid_reserve -= from_liquid;
+			
+			// the fee first makes good any slashed reserve, the rest is surplus
+			let repaid = std::cmp::min(instant_fee, state.slashed_reserve);
+			state.slashed_reserve -= repaid;
+			state.reserve += instant_fee - repaid;
+			
+			let chain_tax = load_chain_tax(deps.as_ref(), &reserve_denom)?.0;
+			messages.push(bank_send(&receiver, net_released_amt, &reserve_denom, &chain_tax));
+			
+			// the tax came out of the liquid reserve as well, so it is already on the contract
+			accrue_tax(deps.storage, &TAX_PENDING, &recipients, &sell_tax)?;
+			instant_paid = true;
+		}
+	}
+	
+	if !instant_paid {
+		// No uluna is returned here since all funds are in the validator
+		// These messages are for documentation only
+		net_released_amt = Uint128::zero();
+		instant_fee = Uint128::zero();
+		
+		// The tax is still bonded, keep it per recipient until the unstake account returns it
+		accrue_tax(deps.storage, &SELL_TAX_ACCRUED, &recipients, &sell_tax)?;
+		
+		// the markers have to arrive as sent, the contract pays the chain tax on top
+		let chain_tax = load_chain_tax(deps.as_ref(), &reserve_denom)?.0;
+		if !special.is_manager(&info.sender) {
+			
+			let unstake_addr = required_acct(&accounts.unstake_acct, "unstake_acct")?;
+			// Build messages(tx) to send transfer 101uluna to track this tx
+			messages.push(bank_send(&unstake_addr, chain_tax.gross_up(Uint128::new(101)), &reserve_denom, &chain_tax));
+		}
+		else{
+			
+			let dexfer_addr = required_acct(&special.dexfer_manager, "dexfer_manager")?;
+			// Build messages(tx) to send transfer 102uluna to track this tx
+			messages.push(bank_send(&dexfer_addr, chain_tax.gross_up(Uint128::new(102)), &reserve_denom, &chain_tax));
+		}
+	}
+
+	// Save the state
+	let breaker_trip = record_trade(deps.storage, &env, before, curve.spot_price(state.supply), state.reserve)?;
+	CURVE_STATE.save(deps.storage, &state)?;
+	
+	// Send Transactions
+	let res = Response::new()
+	.add_messages(messages)
+	.add_attribute("from", info.sender)
+	.add_attribute("BASE Burn: ", amount)
+	.add_attribute("LUNC Unstake: ", released)
+	.add_attribute("LUNC Tax: ", post_tax_amt)
+	.add_attribute("Sell Tax Rate: ", tax_percent.to_string())
+	.add_attribute("Net Unstake: ", net_released_amt);
+	
+	let res = if instant_paid {
+		res.add_attribute("LUNC Instant Fee: ", instant_fee)
+		.add_attribute("Unstake Period: ", "Instant")
+	} else {
+		res.add_attribute("LUNC Tax Accrued: ", post_tax_amt)
+		.add_attribute("Unstake Period: ", "21 Days")
+	};
+	let res = match breaker_trip {
+		Some(reason) => res.add_attribute("Circuit Breaker: ", reason),
+		None => res,
+	};
+	let res = if early_exit {
+		res.add_attribute("Early Exit: ", "true")
+	} else {
+		res
+	};
+	
+	Ok(res)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+	// default implementation stores curve info as enum, you can do something else in a derived
+	// contract and just pass in your custom curve to do_execute
+	let curve_type = CURVE_TYPE.load(deps.storage)?;
+	let curve_fn = curve_type.to_curve_fn();
+	do_query(deps, env, msg, curve_fn)
 }
 
 /// We pull out logic here, so we can import this from another contract and set a different Curve.
 /// This contacts sets a curve with an enum in InstantitateMsg and stored in state, but you may want
 /// to use custom math not included - make this easily reusable
-pub fn do_query(deps: Deps, _env: Env, msg: QueryMsg, curve_fn: CurveFn) -> StdResult<Binary> {
+pub fn do_query(deps: Deps, env: Env, msg: QueryMsg, curve_fn: CurveFn) -> StdResult<Binary> {
 	match msg {
 		// custom queries
 		QueryMsg::CurveInfo {} => to_binary(&query_curve_info(deps, curve_fn)?),
@@ -727,8 +1460,43 @@ pub fn do_query(deps: Deps, _env: Env, msg: QueryMsg, curve_fn: CurveFn) -> StdR
 		QueryMsg::DownloadLogo {} => to_binary(&query_download_logo(deps)?),
 		QueryMsg::ParamInfo {} => to_binary(&query_paraminfo(deps)?),
 		QueryMsg::AcctInfo {} => to_binary(&query_acctinfo(deps)?),
+		QueryMsg::PendingOwner {} => to_binary(&query_pending_owner(deps)?),
+		QueryMsg::PendingChanges { start_after, limit } => {
+			to_binary(&query_pending_changes(deps, start_after, limit)?)
+		}
+		QueryMsg::Roles { start_after, limit } => to_binary(&query_roles(deps, start_after, limit)?),
 		QueryMsg::DexferInfo {} => to_binary(&query_dexferinfo(deps)?),
 		QueryMsg::SafetyInfo {} => to_binary(&query_safetyinfo(deps)?),
+		QueryMsg::Limits { address } => to_binary(&query_limits(deps, env, address)?),
+		QueryMsg::Cooldown { address } => to_binary(&query_cooldown(deps, env, address)?),
+		QueryMsg::HolderTiers { address } => to_binary(&query_holder_tiers(deps, address)?),
+		QueryMsg::SimulateBuy { buyer, amount } => {
+			to_binary(&query_simulate_buy(deps, curve_fn, buyer, amount)?)
+		}
+		QueryMsg::HoldingTax { address } => to_binary(&query_holding_tax(deps, env, address)?),
+		QueryMsg::ComplianceDenylist { start_after, limit } => {
+			to_binary(&query_compliance_denylist(deps, start_after, limit)?)
+		}
+		QueryMsg::WhaleInfo { start_after, limit } => {
+			to_binary(&query_whale_info(deps, start_after, limit)?)
+		}
+		QueryMsg::PauseStatus {} => to_binary(&query_pause_status(deps)?),
+		QueryMsg::CircuitBreakerStatus {} => to_binary(&query_circuit_breaker_status(deps, env)?),
+		QueryMsg::ReserveHealth {} => to_binary(&query_reserve_health(deps, env)?),
+		QueryMsg::LiquidityInfo {} => to_binary(&query_liquidityinfo(deps)?),
+		QueryMsg::TaxAccrual {} => to_binary(&query_tax_accrual(deps)?),
+		QueryMsg::ChainTaxInfo {} => to_binary(&query_chain_tax_info(deps)?),
+		QueryMsg::TaxRecipients {} => to_binary(&query_tax_recipients(deps)?),
+		QueryMsg::PendingTaxes {} => to_binary(&query_pending_taxes(deps, env)?),
+		QueryMsg::AffiliateInfo { code } => to_binary(&query_affiliate_info(deps, code)?),
+		QueryMsg::AffiliateTiers {} => to_binary(&query_affiliate_tiers(deps)?),
+		QueryMsg::AffiliateRules { buyer } => to_binary(&query_affiliate_rules(deps, buyer)?),
+		QueryMsg::AffiliateDenylist { start_after, limit } => {
+			to_binary(&query_affiliate_denylist(deps, start_after, limit)?)
+		}
+		QueryMsg::Affiliates { start_after, limit } => {
+			to_binary(&query_affiliates(deps, start_after, limit)?)
+		}
 	}
 }
 
@@ -739,6 +1507,8 @@ pub fn query_curve_info(deps: Deps, curve_fn: CurveFn) -> StdResult<CurveInfoRes
 		reserve_denom,
 		decimals,
 		tax_collected,
+		reserve_burned,
+		..
 	} = CURVE_STATE.load(deps.storage)?;
 	
 	// This we can get from the local digits stored in instantiate
@@ -751,28 +1521,24 @@ pub fn query_curve_info(deps: Deps, curve_fn: CurveFn) -> StdResult<CurveInfoRes
 		spot_price,
 		reserve_denom,
 		tax_collected,
+		reserve_burned,
 	})
 }
 
 pub fn query_paraminfo(deps: Deps) -> StdResult<ParamInfoResponse> {
 	let ParamConfig {
-		yield_percent,
-		burn_percent,
-		social_percent,
-		expense_percent,
 		affiliate_percent,
 		tax_percent,
 		presale_price,
 	} = PARAM_CONFIG.load(deps.storage)?;
+	let ceilings = PARAM_CEILINGS.may_load(deps.storage)?.unwrap_or_default();
 	
 	Ok(ParamInfoResponse {
-		yield_percent,
-		burn_percent,
-		social_percent,
-		expense_percent,
 		affiliate_percent,
 		tax_percent,
 		presale_price,
+		max_tax_percent: ceilings.max_tax_percent,
+		max_affiliate_percent: ceilings.max_affiliate_percent,
 	})
 }
 
@@ -780,28 +1546,27 @@ pub fn execute_update_paramconfig(
 deps: DepsMut,
 _env: Env,
 info: MessageInfo,
-yield_percent: u32,
-burn_percent: u32,
-social_percent: u32,
-expense_percent: u32,
 affiliate_percent: u32,
 tax_percent: u32,
 presale_price: u32,
 ) -> Result<Response, ContractError> {
 	
-	//Only owner is authorized to proceed
-	let accounts = ACCT_CONFIG.load(deps.storage)?;
-	if &accounts.owner != &info.sender {
-		return Err(ContractError::Unauthorized{});
+	//Only owner or FeeManager is authorized to proceed
+	assert_role(deps.storage, &info.sender, Role::FeeManager)?;
+	
+	check_percent("affiliate_percent", affiliate_percent)?;
+	check_percent("tax_percent", tax_percent)?;
+	let ceilings = PARAM_CEILINGS.may_load(deps.storage)?.unwrap_or_default();
+	check_ceiling("affiliate_percent", affiliate_percent, ceilings.max_affiliate_percent)?;
+	check_ceiling("tax_percent", tax_percent, ceilings.max_tax_percent)?;
+	// a tax nobody receives could never be split
+	if tax_percent != 0 && TAX_RECIPIENTS.may_load(deps.storage)?.unwrap_or_default().is_empty() {
+		return Err(ContractError::NoTaxRecipients {});
 	}
 	
 	let mut config = PARAM_CONFIG
 	.may_load(deps.storage)?
 	.ok_or(ContractError::Unauthorized {})?;
-	config.yield_percent = yield_percent;
-	config.burn_percent = burn_percent;
-	config.social_percent = social_percent;
-	config.expense_percent = expense_percent;
 	config.affiliate_percent = affiliate_percent;
 	config.tax_percent = tax_percent;
 	config.presale_price = presale_price;
@@ -811,186 +1576,1876 @@ presale_price: u32,
 	Ok(Response::default())
 }
 
-pub fn query_acctinfo(deps: Deps) -> StdResult<AcctInfoResponse> {
-	let AcctConfig {
-		owner,
-		presale_acct,
-		yield_acct,
-		burn_acct,
-		social_acct,
-		expense_acct,
-		stake_acct,
-		unstake_acct,
-	} = ACCT_CONFIG.load(deps.storage)?;
-	
-	Ok(AcctInfoResponse {
-		owner,
-		presale_acct,
-		yield_acct,
-		burn_acct,
-		social_acct,
-		expense_acct,
-		stake_acct,
-		unstake_acct,
-	})
-}
-
-pub fn execute_update_acctconfig(
+pub fn execute_lower_param_ceilings(
 deps: DepsMut,
 _env: Env,
 info: MessageInfo,
-presale_acct: String,
-yield_acct: String,
-burn_acct: String,
-social_acct: String,
-expense_acct: String,
-stake_acct: String,
-unstake_acct: String,
+max_tax_percent: u32,
+max_affiliate_percent: u32,
 ) -> Result<Response, ContractError> {
 	
 	//Only owner is authorized to proceed
-	let accounts = ACCT_CONFIG.load(deps.storage)?;
-	if &accounts.owner != &info.sender {
-		return Err(ContractError::Unauthorized{});
+	assert_owner(deps.storage, &info.sender)?;
+	
+	let mut ceilings = PARAM_CEILINGS.may_load(deps.storage)?.unwrap_or_default();
+	if max_tax_percent > ceilings.max_tax_percent {
+		return Err(ContractError::CeilingRaised {
+			field: "max_tax_percent".to_string(),
+			ceiling: ceilings.max_tax_percent,
+		});
+	}
+	if max_affiliate_percent > ceilings.max_affiliate_percent {
+		return Err(ContractError::CeilingRaised {
+			field: "max_affiliate_percent".to_string(),
+			ceiling: ceilings.max_affiliate_percent,
+		});
 	}
 	
-	let mut config = ACCT_CONFIG
-	.may_load(deps.storage)?
-	.ok_or(ContractError::Unauthorized {})?;
-	config.presale_acct = presale_acct;
-	config.yield_acct = yield_acct;
-	config.burn_acct = burn_acct;
-	config.social_acct = social_acct;
-	config.expense_acct = expense_acct;
-	config.stake_acct = stake_acct;
-	config.unstake_acct = unstake_acct;
-	// Save config back to contract storage.
-	ACCT_CONFIG.save(deps.storage, &config)?;
+	// the live params have to fit under the new ceilings
+	let params = PARAM_CONFIG.load(deps.storage)?;
+	check_ceiling("tax_percent", params.tax_percent, max_tax_percent)?;
+	check_ceiling("affiliate_percent", params.affiliate_percent, max_affiliate_percent)?;
+	for tier in AFFILIATE_TIERS.may_load(deps.storage)?.unwrap_or_default() {
+		check_ceiling("affiliate_percent", tier.affiliate_percent, max_affiliate_percent)?;
+	}
 	
-	Ok(Response::default())
+	ceilings.max_tax_percent = max_tax_percent;
+	ceilings.max_affiliate_percent = max_affiliate_percent;
+	PARAM_CEILINGS.save(deps.storage, &ceilings)?;
+	
+	Ok(Response::new()
+	.add_attribute("action", "lower_param_ceilings")
+	.add_attribute("max_tax_percent", max_tax_percent.to_string())
+	.add_attribute("max_affiliate_percent", max_affiliate_percent.to_string()))
 }
 
-pub fn query_dexferinfo(deps: Deps) -> StdResult<DexferInfoResponse> {
-	let DexferConfig {
-		dexfer_manager,
-		deposit_percent,
-		tax_exempt,
-		token_minter,
-	} = DEXFER_CONFIG.load(deps.storage)?;
-	
-	Ok(DexferInfoResponse {
-		dexfer_manager,
-		deposit_percent,
-		tax_exempt,
-		token_minter,
-	})
+/// Only the current owner passes, nobody once ownership is renounced
+fn assert_owner(storage: &dyn Storage, sender: &Addr) -> Result<(), ContractError> {
+	let accounts = ACCT_CONFIG.load(storage)?;
+	if accounts.owner.as_ref() != Some(sender) {
+		return Err(ContractError::Unauthorized{});
+	}
+	Ok(())
 }
 
-pub fn execute_update_dexferconfig(
+/// The owner or anyone the owner gave role
+fn assert_role(storage: &dyn Storage, sender: &Addr, role: Role) -> Result<(), ContractError> {
+	if assert_owner(storage, sender).is_ok() {
+		return Ok(());
+	}
+	let roles = ROLES.may_load(storage, sender)?.unwrap_or_default();
+	if !roles.contains(&role) {
+		return Err(ContractError::MissingRole { role: role.as_str().to_string() });
+	}
+	Ok(())
+}
+
+pub fn execute_update_roles(
 deps: DepsMut,
 _env: Env,
 info: MessageInfo,
-dexfer_manager: String,
-deposit_percent: u32,
-tax_exempt: String,
-token_minter: String,
+address: String,
+roles: Vec<Role>,
 ) -> Result<Response, ContractError> {
 	
 	//Only owner is authorized to proceed
-	let accounts = ACCT_CONFIG.load(deps.storage)?;
-	if &accounts.owner != &info.sender {
-		return Err(ContractError::Unauthorized{});
-	}
+	assert_owner(deps.storage, &info.sender)?;
 	
-	let mut config = DEXFER_CONFIG
-	.may_load(deps.storage)?
-	.ok_or(ContractError::Unauthorized {})?;
-	config.dexfer_manager = dexfer_manager;
-	config.deposit_percent = deposit_percent;
-	config.tax_exempt = tax_exempt;
-	config.token_minter = token_minter;
-	// Save config back to contract storage.
-	DEXFER_CONFIG.save(deps.storage, &config)?;
+	let addr = deps.api.addr_validate(&address)?;
+	let mut deduped: Vec<Role> = vec![];
+	for role in roles {
+		if !deduped.contains(&role) {
+			deduped.push(role);
+		}
+	}
+	if deduped.is_empty() {
+		ROLES.remove(deps.storage, &addr);
+	} else {
+		ROLES.save(deps.storage, &addr, &deduped)?;
+	}
 	
-	Ok(Response::default())
+	let names: Vec<&str> = deduped.iter().map(|r| r.as_str()).collect();
+	Ok(Response::new()
+	.add_attribute("action", "update_roles")
+	.add_attribute("address", addr)
+	.add_attribute("roles", names.join(",")))
 }
 
-pub fn query_safetyinfo(deps: Deps) -> StdResult<SafetyInfoResponse> {
-	let SafetyConfig {
-		can_buy,
-		can_sell,
-	} = SAFETY_CONFIG.load(deps.storage)?;
+pub fn query_roles(
+deps: Deps,
+start_after: Option<String>,
+limit: Option<u32>,
+) -> StdResult<RolesResponse> {
+	let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+	let start = start_after.map(Bound::exclusive);
+	
+	let assignments = ROLES
+	.range(deps.storage, start, None, Order::Ascending)
+	.take(limit)
+	.map(|item| {
+		let (address, roles) = item?;
+		Ok(RoleAssignment {
+			address: Addr::unchecked(String::from_utf8(address)?),
+			roles,
+		})
+	})
+	.collect::<StdResult<Vec<_>>>()?;
 	
-	Ok(SafetyInfoResponse {
-		can_buy,
-		can_sell,
+	Ok(RolesResponse { assignments })
+}
+
+pub fn query_pending_owner(deps: Deps) -> StdResult<PendingOwnerResponse> {
+	let proposal = PENDING_OWNER.may_load(deps.storage)?;
+	Ok(PendingOwnerResponse {
+		owner: ACCT_CONFIG.load(deps.storage)?.owner,
+		pending_owner: proposal.as_ref().map(|p| p.owner.clone()),
+		expiry: proposal.and_then(|p| p.expiry),
 	})
 }
 
-pub fn execute_update_safetyconfig(
+pub fn execute_propose_new_owner(
 deps: DepsMut,
 _env: Env,
 info: MessageInfo,
-can_buy: String,
-can_sell: String,
+owner: String,
+expiry: Option<Expiration>,
 ) -> Result<Response, ContractError> {
 	
 	//Only owner is authorized to proceed
-	let accounts = ACCT_CONFIG.load(deps.storage)?;
-	if &accounts.owner != &info.sender {
+	assert_owner(deps.storage, &info.sender)?;
+	
+	let owner = deps.api.addr_validate(&owner)?;
+	PENDING_OWNER.save(deps.storage, &OwnershipProposal {
+		owner: owner.clone(),
+		expiry,
+	})?;
+	
+	Ok(Response::new()
+	.add_attribute("action", "propose_new_owner")
+	.add_attribute("pending_owner", owner))
+}
+
+pub fn execute_accept_ownership(
+deps: DepsMut,
+env: Env,
+info: MessageInfo,
+) -> Result<Response, ContractError> {
+	let proposal = PENDING_OWNER
+	.may_load(deps.storage)?
+	.ok_or(ContractError::NoOwnershipProposal {})?;
+	if proposal.owner != info.sender {
 		return Err(ContractError::Unauthorized{});
 	}
+	if let Some(expiry) = proposal.expiry {
+		if expiry.is_expired(&env.block) {
+			return Err(ContractError::OwnershipProposalExpired {});
+		}
+	}
 	
-	let mut config = SAFETY_CONFIG
+	let mut accounts = ACCT_CONFIG.load(deps.storage)?;
+	accounts.owner = Some(proposal.owner);
+	ACCT_CONFIG.save(deps.storage, &accounts)?;
+	PENDING_OWNER.remove(deps.storage);
+	
+	Ok(Response::new()
+	.add_attribute("action", "accept_ownership")
+	.add_attribute("owner", info.sender))
+}
+
+pub fn execute_cancel_ownership_proposal(
+deps: DepsMut,
+_env: Env,
+info: MessageInfo,
+) -> Result<Response, ContractError> {
+	
+	//Only owner is authorized to proceed
+	assert_owner(deps.storage, &info.sender)?;
+	
+	if PENDING_OWNER.may_load(deps.storage)?.is_none() {
+		return Err(ContractError::NoOwnershipProposal {});
+	}
+	PENDING_OWNER.remove(deps.storage);
+	
+	Ok(Response::new().add_attribute("action", "cancel_ownership_proposal"))
+}
+
+/// Leaves the contract without owner or role holders for good, every admin message is refused after this
+pub fn execute_renounce_ownership(
+deps: DepsMut,
+_env: Env,
+info: MessageInfo,
+) -> Result<Response, ContractError> {
+	
+	//Only owner is authorized to proceed
+	assert_owner(deps.storage, &info.sender)?;
+	
+	let mut accounts = ACCT_CONFIG.load(deps.storage)?;
+	accounts.owner = None;
+	ACCT_CONFIG.save(deps.storage, &accounts)?;
+	PENDING_OWNER.remove(deps.storage);
+	
+	// roles would keep admin messages open, they go with the owner
+	let holders = ROLES
+	.keys(deps.storage, None, None, Order::Ascending)
+	.collect::<Vec<_>>();
+	for holder in holders {
+		ROLES.remove(deps.storage, &Addr::unchecked(String::from_utf8(holder).map_err(StdError::from)?));
+	}
+	
+	Ok(Response::new().add_attribute("action", "renounce_ownership"))
+}
+
+fn required_acct(acct: &Option<Addr>, name: &str) -> Result<Addr, ContractError> {
+	acct.clone().ok_or(ContractError::AccountNotSet {
+		account: name.to_string(),
+	})
+}
+
+/// Per 1000 settings can't go over the whole
+fn check_percent(field: &str, value: u32) -> Result<(), ContractError> {
+	if value > 1000 {
+		return Err(ContractError::InvalidPercentage {
+			field: field.to_string(),
+			value,
+		});
+	}
+	Ok(())
+}
+
+fn check_ceiling(field: &str, value: u32, ceiling: u32) -> Result<(), ContractError> {
+	if value > ceiling {
+		return Err(ContractError::AboveCeiling {
+			field: field.to_string(),
+			value,
+			ceiling,
+		});
+	}
+	Ok(())
+}
+
+pub fn query_acctinfo(deps: Deps) -> StdResult<AcctInfoResponse> {
+	let AcctConfig {
+		owner,
+		presale_acct,
+		stake_acct,
+		unstake_acct,
+	} = ACCT_CONFIG.load(deps.storage)?;
+	
+	Ok(AcctInfoResponse {
+		owner,
+		presale_acct,
+		stake_acct,
+		unstake_acct,
+	})
+}
+
+pub fn execute_update_acctconfig(
+deps: DepsMut,
+_env: Env,
+info: MessageInfo,
+presale_acct: Option<String>,
+stake_acct: Option<String>,
+unstake_acct: Option<String>,
+) -> Result<Response, ContractError> {
+	
+	//Only owner or AccountManager is authorized to proceed
+	assert_role(deps.storage, &info.sender, Role::AccountManager)?;
+	
+	let mut config = ACCT_CONFIG
 	.may_load(deps.storage)?
 	.ok_or(ContractError::Unauthorized {})?;
-	config.can_buy = can_buy;
-	config.can_sell = can_sell;
+	config.presale_acct = updated_acct(deps.api, config.presale_acct, presale_acct)?;
+	config.stake_acct = updated_acct(deps.api, config.stake_acct, stake_acct)?;
+	config.unstake_acct = updated_acct(deps.api, config.unstake_acct, unstake_acct)?;
 	// Save config back to contract storage.
-	SAFETY_CONFIG.save(deps.storage, &config)?;
+	ACCT_CONFIG.save(deps.storage, &config)?;
 	
 	Ok(Response::default())
 }
 
-pub fn execute_update_minter(
+/// None keeps the current account, an empty address unsets it
+fn updated_acct(api: &dyn Api, current: Option<Addr>, update: Option<String>) -> StdResult<Option<Addr>> {
+	match update {
+		None => Ok(current),
+		Some(acct) if acct.is_empty() => Ok(None),
+		Some(acct) => Ok(Some(api.addr_validate(&acct)?)),
+	}
+}
+
+pub fn query_tax_recipients(deps: Deps) -> StdResult<TaxRecipientsResponse> {
+	let recipients = TAX_RECIPIENTS
+	.may_load(deps.storage)?
+	.unwrap_or_default()
+	.into_iter()
+	.map(|r| {
+		let total_paid = TAX_TOTALS.may_load(deps.storage, &r.label)?.unwrap_or_default();
+		Ok(TaxRecipientInfo {
+			addr: r.addr,
+			share_per_mille: r.share_per_mille,
+			label: r.label,
+			total_paid,
+		})
+	})
+	.collect::<StdResult<Vec<_>>>()?;
+	
+	Ok(TaxRecipientsResponse { recipients })
+}
+
+/// Labels have to be unique and not empty, shares have to add up to 1000
+fn checked_tax_recipients(api: &dyn Api, recipients: Vec<TaxRecipientMsg>) -> Result<Vec<TaxRecipient>, ContractError> {
+	let mut validated: Vec<TaxRecipient> = vec![];
+	for r in recipients {
+		if r.label.is_empty() || validated.iter().any(|v| v.label == r.label) {
+			return Err(ContractError::InvalidTaxLabel { label: r.label });
+		}
+		// bounded so the total below can't overflow
+		check_percent("share_per_mille", r.share_per_mille)?;
+		let addr = r.addr.map(|a| api.addr_validate(&a)).transpose()?;
+		validated.push(TaxRecipient {
+			addr,
+			share_per_mille: r.share_per_mille,
+			label: r.label,
+		});
+	}
+	
+	let total = validated.iter().map(|r| r.share_per_mille).sum::<u32>();
+	if total != 1000 {
+		return Err(ContractError::InvalidTaxSplit { total });
+	}
+	Ok(validated)
+}
+
+pub fn execute_update_tax_recipients(
 deps: DepsMut,
 _env: Env,
 info: MessageInfo,
-new_minter: Option<String>,
+recipients: Vec<TaxRecipientMsg>,
 ) -> Result<Response, ContractError> {
 	
-	let accounts = ACCT_CONFIG.load(deps.storage)?;
-	let mut config = TOKEN_INFO
+	//Only owner or AccountManager is authorized to proceed
+	assert_role(deps.storage, &info.sender, Role::AccountManager)?;
+	
+	let validated = checked_tax_recipients(deps.api, recipients)?;
+	
+	// tax not yet distributed has to reach the recipient it was accrued for
+	for old in TAX_RECIPIENTS.may_load(deps.storage)?.unwrap_or_default() {
+		let owed = SELL_TAX_ACCRUED.may_load(deps.storage, &old.label)?.unwrap_or_default()
+		+ TAX_PENDING.may_load(deps.storage, &old.label)?.unwrap_or_default();
+		if !owed.is_zero() && !validated.iter().any(|v| v.label == old.label) {
+			return Err(ContractError::TaxStillAccrued { label: old.label });
+		}
+	}
+	
+	TAX_RECIPIENTS.save(deps.storage, &validated)?;
+	
+	Ok(Response::new()
+	.add_attribute("action", "update_tax_recipients")
+	.add_attribute("recipients", validated.len().to_string()))
+}
+
+pub fn query_dexferinfo(deps: Deps) -> StdResult<DexferInfoResponse> {
+	let DexferConfig {
+		dexfer_manager,
+		deposit_percent,
+		tax_exempt,
+		token_minter,
+	} = DEXFER_CONFIG.load(deps.storage)?;
+	
+	Ok(DexferInfoResponse {
+		dexfer_manager,
+		deposit_percent,
+		tax_exempt,
+		token_minter,
+	})
+}
+
+pub fn execute_update_dexferconfig(
+deps: DepsMut,
+_env: Env,
+info: MessageInfo,
+dexfer_manager: Option<String>,
+deposit_percent: u32,
+tax_exempt: Option<String>,
+token_minter: String,
+) -> Result<Response, ContractError> {
+	
+	//Only owner or AccountManager is authorized to proceed
+	assert_role(deps.storage, &info.sender, Role::AccountManager)?;
+	
+	check_percent("deposit_percent", deposit_percent)?;
+	
+	let mut config = DEXFER_CONFIG
 	.may_load(deps.storage)?
 	.ok_or(ContractError::Unauthorized {})?;
+	config.dexfer_manager = updated_acct(deps.api, config.dexfer_manager, dexfer_manager)?;
+	config.deposit_percent = deposit_percent;
+	config.tax_exempt = updated_acct(deps.api, config.tax_exempt, tax_exempt)?;
+	config.token_minter = token_minter;
+	// Save config back to contract storage.
+	DEXFER_CONFIG.save(deps.storage, &config)?;
 	
-	let mint = config.mint.as_ref().ok_or(ContractError::Unauthorized {})?;
-	//if mint.minter != info.sender {
-	if &accounts.owner != &info.sender {
-		return Err(ContractError::Unauthorized {});
+	Ok(Response::default())
+}
+
+pub fn query_safetyinfo(deps: Deps) -> StdResult<SafetyInfoResponse> {
+	let SafetyConfig {
+		status,
+	} = SAFETY_CONFIG.load(deps.storage)?;
+	
+	Ok(SafetyInfoResponse {
+		status,
+	})
+}
+
+/// Validates the addresses of an allowlist
+fn checked_status(api: &dyn Api, status: TradingStatus) -> StdResult<TradingStatus> {
+	Ok(match status {
+		TradingStatus::Allowlist(allowed) => TradingStatus::Allowlist(
+			allowed
+			.iter()
+			.map(|a| api.addr_validate(a.as_str()))
+			.collect::<StdResult<Vec<_>>>()?,
+		),
+		status => status,
+	})
+}
+
+pub fn execute_update_safetyconfig(
+deps: DepsMut,
+env: Env,
+info: MessageInfo,
+status: TradingStatus,
+) -> Result<Response, ContractError> {
+	
+	//Only owner or Pauser is authorized to proceed
+	assert_role(deps.storage, &info.sender, Role::Pauser)?;
+	
+	let mut config = SAFETY_CONFIG
+	.may_load(deps.storage)?
+	.ok_or(ContractError::Unauthorized {})?;
+	config.status = checked_status(deps.api, status)?;
+	// Save config back to contract storage.
+	SAFETY_CONFIG.save(deps.storage, &config)?;
+	
+	let status_name = match config.status {
+		TradingStatus::Open => "open",
+		TradingStatus::Paused => "paused",
+		TradingStatus::Allowlist(_) => "allowlist",
+		TradingStatus::SellOnly => "sell_only",
+	};
+	let event = Event::new("pause")
+	.add_attribute("pause_action", "trading_status")
+	.add_attribute("status", status_name)
+	.add_attribute("by", info.sender)
+	.add_attribute("height", env.block.height.to_string());
+	
+	Ok(Response::new()
+	.add_attribute("action", "update_safetyconfig")
+	.add_event(event))
+}
+
+// seconds in the per address buy window
+const DAY_SECONDS: u64 = 86_400;
+
+/// Checks the buy against BuyLimits and books it against the address and block caps
+fn use_buy_limits(
+storage: &mut dyn Storage,
+env: &Env,
+buyer: &Addr,
+amount: Uint128,
+) -> Result<(), ContractError> {
+	let limits = BUY_LIMITS.may_load(storage)?.unwrap_or_default();
+	if amount < limits.min_buy {
+		return Err(ContractError::BuyTooSmall { min: limits.min_buy });
+	}
+	if let Some(max) = limits.max_buy {
+		if amount > max {
+			return Err(ContractError::BuyTooLarge { max });
+		}
 	}
 	
-	let minter_data = new_minter
-	.map(|new_minter| deps.api.addr_validate(&new_minter))
-	.transpose()?
-	.map(|minter| MinterData {
-		minter,
-		cap: mint.cap,
-	});
+	if let Some(cap) = limits.address_daily_cap {
+		let mut usage = address_usage(storage, env, buyer)?;
+		let remaining = cap.checked_sub(usage.amount).unwrap_or_else(|_| Uint128::zero());
+		if amount > remaining {
+			return Err(ContractError::AddressCapExceeded { remaining });
+		}
+		usage.amount += amount;
+		ADDRESS_BUY_USAGE.save(storage, buyer, &usage)?;
+	}
 	
-	config.mint = minter_data;
+	if let Some(cap) = limits.block_cap {
+		let mut usage = block_usage(storage, env)?;
+		let remaining = cap.checked_sub(usage.amount).unwrap_or_else(|_| Uint128::zero());
+		if amount > remaining {
+			return Err(ContractError::BlockCapExceeded { remaining });
+		}
+		usage.amount += amount;
+		BLOCK_BUY_USAGE.save(storage, &usage)?;
+	}
+	Ok(())
+}
+
+/// Usage in the address' current 24h window, a fresh window once the last one ran out
+fn address_usage(storage: &dyn Storage, env: &Env, buyer: &Addr) -> StdResult<BuyUsage> {
+	let now = env.block.time.seconds();
+	let usage = ADDRESS_BUY_USAGE.may_load(storage, buyer)?;
+	Ok(match usage {
+		Some(usage) if now < usage.window_start + DAY_SECONDS => usage,
+		_ => BuyUsage { window_start: now, amount: Uint128::zero() },
+	})
+}
+
+fn block_usage(storage: &dyn Storage, env: &Env) -> StdResult<BuyUsage> {
+	let height = env.block.height;
+	let usage = BLOCK_BUY_USAGE.may_load(storage)?;
+	Ok(match usage {
+		Some(usage) if usage.window_start == height => usage,
+		_ => BuyUsage { window_start: height, amount: Uint128::zero() },
+	})
+}
+
+pub fn query_limits(deps: Deps, env: Env, address: String) -> StdResult<LimitsResponse> {
+	let buyer = deps.api.addr_validate(&address)?;
+	let limits = BUY_LIMITS.may_load(deps.storage)?.unwrap_or_default();
+	let address_used = address_usage(deps.storage, &env, &buyer)?;
+	let block_used = block_usage(deps.storage, &env)?.amount;
+	let remaining = |cap: Uint128, used: Uint128| cap.checked_sub(used).unwrap_or_else(|_| Uint128::zero());
+	
+	Ok(LimitsResponse {
+		min_buy: limits.min_buy,
+		max_buy: limits.max_buy,
+		address_daily_cap: limits.address_daily_cap,
+		address_used: address_used.amount,
+		address_remaining: limits.address_daily_cap.map(|cap| remaining(cap, address_used.amount)),
+		address_resets_at: ADDRESS_BUY_USAGE
+		.may_load(deps.storage, &buyer)?
+		.map(|usage| usage.window_start + DAY_SECONDS)
+		.filter(|reset| *reset > env.block.time.seconds()),
+		block_cap: limits.block_cap,
+		block_used,
+		block_remaining: limits.block_cap.map(|cap| remaining(cap, block_used)),
+	})
+}
+
+/// Moves holder's acquisition time towards now by the share amount has of its balance after
+/// receiving it. Must run before the tokens are credited
+fn record_acquisition(storage: &mut dyn Storage, env: &Env, holder: &Addr, amount: Uint128) -> StdResult<()> {
+	let now = env.block.time.seconds();
+	let held = BALANCES.may_load(storage, holder)?.unwrap_or_default();
+	// balances from before tracking count as acquired at 0
+	let acquired_at = ACQUIRED_AT.may_load(storage, holder)?.unwrap_or_default();
+	let total = held + amount;
+	if total.is_zero() {
+		return Ok(());
+	}
+	// moved towards now by the incoming share, rounding once
+	let moved = Uint128::from(now.saturating_sub(acquired_at)).multiply_ratio(amount, total);
+	ACQUIRED_AT.save(storage, holder, &(acquired_at + moved.u128() as u64))
+}
+
+/// Sell tax for seller from the holding tax schedule, base_percent past the last step
+fn holding_tax_percent(storage: &dyn Storage, env: &Env, seller: &Addr, base_percent: u32) -> StdResult<u32> {
+	let steps = HOLDING_TAX_SCHEDULE.may_load(storage)?.unwrap_or_default();
+	let acquired_at = ACQUIRED_AT.may_load(storage, seller)?.unwrap_or_default();
+	let held = env.block.time.seconds().saturating_sub(acquired_at);
+	Ok(steps
+	.iter()
+	.find(|step| held < step.max_age_seconds)
+	.map(|step| step.tax_percent)
+	.unwrap_or(base_percent))
+}
+
+pub fn query_holding_tax(deps: Deps, env: Env, address: Option<String>) -> StdResult<HoldingTaxResponse> {
+	let base_percent = PARAM_CONFIG.load(deps.storage)?.tax_percent;
+	let (acquired_at, tax_percent) = match address {
+		Some(address) => {
+			let seller = deps.api.addr_validate(&address)?;
+			(
+				ACQUIRED_AT.may_load(deps.storage, &seller)?,
+				Some(holding_tax_percent(deps.storage, &env, &seller, base_percent)?),
+			)
+		}
+		None => (None, None),
+	};
 	
-	TOKEN_INFO.save(deps.storage, &config)?;
+	Ok(HoldingTaxResponse {
+		base_percent,
+		steps: HOLDING_TAX_SCHEDULE.may_load(deps.storage)?.unwrap_or_default(),
+		acquired_at,
+		tax_percent,
+	})
+}
+
+pub fn execute_update_holding_tax_schedule(
+deps: DepsMut,
+_env: Env,
+info: MessageInfo,
+steps: Vec<HoldingTaxStep>,
+) -> Result<Response, ContractError> {
 	
-	Ok(Response::default()
-	.add_attribute("action", "update_minter")
-	.add_attribute(
-	"new_minter",
-	config
-	.mint
-	.map(|m| m.minter.into_string())
-	.unwrap_or_else(|| "None".to_string()),
-	))
+	//Only owner or FeeManager is authorized to proceed
+	assert_role(deps.storage, &info.sender, Role::FeeManager)?;
+	
+	let ceilings = PARAM_CEILINGS.may_load(deps.storage)?.unwrap_or_default();
+	let mut last_age = 0u64;
+	for step in steps.iter() {
+		if step.max_age_seconds <= last_age {
+			return Err(ContractError::InvalidHoldingTaxSchedule {});
+		}
+		last_age = step.max_age_seconds;
+		check_percent("tax_percent", step.tax_percent)?;
+		check_ceiling("tax_percent", step.tax_percent, ceilings.max_tax_percent)?;
+	}
+	HOLDING_TAX_SCHEDULE.save(deps.storage, &steps)?;
+	
+	Ok(Response::new()
+	.add_attribute("action", "update_holding_tax_schedule")
+	.add_attribute("steps", steps.len().to_string()))
+}
+
+/// Tokens moved on keep the later of the two last buys, so passing them to a fresh address doesn't skip the cooldown
+fn carry_last_buy(storage: &mut dyn Storage, from: &Addr, to: &Addr) -> StdResult<()> {
+	if let Some(last_buy) = LAST_BUY.may_load(storage, from)? {
+		if LAST_BUY.may_load(storage, to)?.unwrap_or_default() < last_buy {
+			LAST_BUY.save(storage, to, &last_buy)?;
+		}
+	}
+	Ok(())
+}
+
+/// Block time in seconds when a sell by seller stops being an early exit, None when it isn't one
+fn cooldown_ends(storage: &dyn Storage, env: &Env, seller: &Addr) -> StdResult<Option<u64>> {
+	let cooldown = COOLDOWN_CONFIG.may_load(storage)?.unwrap_or_default();
+	if cooldown.cooldown_seconds == 0 {
+		return Ok(None);
+	}
+	Ok(LAST_BUY
+	.may_load(storage, seller)?
+	.map(|last_buy| last_buy + cooldown.cooldown_seconds)
+	.filter(|ends_at| *ends_at > env.block.time.seconds()))
+}
+
+pub fn query_cooldown(deps: Deps, env: Env, address: String) -> StdResult<CooldownResponse> {
+	let seller = deps.api.addr_validate(&address)?;
+	let cooldown = COOLDOWN_CONFIG.may_load(deps.storage)?.unwrap_or_default();
+	
+	Ok(CooldownResponse {
+		cooldown_seconds: cooldown.cooldown_seconds,
+		early_exit_tax_percent: cooldown.early_exit_tax_percent,
+		last_buy: LAST_BUY.may_load(deps.storage, &seller)?,
+		ends_at: cooldown_ends(deps.storage, &env, &seller)?,
+	})
+}
+
+pub fn execute_update_cooldown(
+deps: DepsMut,
+_env: Env,
+info: MessageInfo,
+cooldown_seconds: u64,
+early_exit_tax_percent: Option<u32>,
+) -> Result<Response, ContractError> {
+	
+	//Only owner or FeeManager is authorized to proceed
+	assert_role(deps.storage, &info.sender, Role::FeeManager)?;
+	
+	if let Some(percent) = early_exit_tax_percent {
+		check_percent("early_exit_tax_percent", percent)?;
+		let ceilings = PARAM_CEILINGS.may_load(deps.storage)?.unwrap_or_default();
+		check_ceiling("early_exit_tax_percent", percent, ceilings.max_tax_percent)?;
+	}
+	
+	let config = CooldownConfig {
+		cooldown_seconds,
+		early_exit_tax_percent,
+	};
+	// Save config back to contract storage.
+	COOLDOWN_CONFIG.save(deps.storage, &config)?;
+	
+	Ok(Response::default())
+}
+
+pub fn execute_update_buylimits(
+deps: DepsMut,
+_env: Env,
+info: MessageInfo,
+min_buy: Uint128,
+max_buy: Option<Uint128>,
+address_daily_cap: Option<Uint128>,
+block_cap: Option<Uint128>,
+) -> Result<Response, ContractError> {
+	
+	//Only owner or FeeManager is authorized to proceed
+	assert_role(deps.storage, &info.sender, Role::FeeManager)?;
+	
+	if let Some(max_buy) = max_buy {
+		if min_buy > max_buy {
+			return Err(ContractError::InvalidBuyLimits { min_buy, max_buy });
+		}
+	}
+	
+	let config = BuyLimits {
+		min_buy,
+		max_buy,
+		address_daily_cap,
+		block_cap,
+	};
+	// Save config back to contract storage.
+	BUY_LIMITS.save(deps.storage, &config)?;
+	
+	Ok(Response::default())
+}
+
+// most trades the circuit breaker keeps in its window
+const MAX_PRICE_SAMPLES: usize = 100;
+
+/// Refuses trades while the breaker is tripped, and clears it once the cooldown is over
+fn check_circuit_breaker(storage: &mut dyn Storage, env: &Env) -> Result<(), ContractError> {
+	if let Some(trip) = BREAKER_TRIP.may_load(storage)? {
+		if env.block.time.seconds() < trip.resets_at {
+			return Err(ContractError::CircuitBreakerTripped {
+				reason: trip.reason,
+				resets_at: trip.resets_at,
+			});
+		}
+		// the samples that tripped it would trip it again
+		BREAKER_TRIP.remove(storage);
+		PRICE_SAMPLES.remove(storage);
+	}
+	Ok(())
+}
+
+/// Adds the trade to the window and trips the breaker when spot price or reserve moved too far
+/// within it. The trade itself still goes through, the breaker stops the ones after it.
+/// An empty window starts from the price and reserve before the trade, so one large trade is measured too
+fn record_trade(
+storage: &mut dyn Storage,
+env: &Env,
+before: (Uint128, Uint128),
+spot_price: Uint128,
+reserve: Uint128,
+) -> StdResult<Option<String>> {
+	let config = CIRCUIT_BREAKER_CONFIG.may_load(storage)?.unwrap_or_default();
+	if config.max_price_move_percent == 0 && config.max_reserve_move_percent == 0 {
+		return Ok(None);
+	}
+	
+	let height = env.block.height;
+	let time = env.block.time.seconds();
+	let mut samples = PRICE_SAMPLES.may_load(storage)?.unwrap_or_default();
+	samples.retain(|s| match config.window {
+		BreakerWindow::Blocks(blocks) => s.height + blocks >= height,
+		BreakerWindow::Seconds(seconds) => s.time + seconds >= time,
+	});
+	if samples.is_empty() {
+		let (spot_price, reserve) = before;
+		samples.push(PriceSample {
+			height,
+			time,
+			spot_price,
+			reserve,
+		});
+	}
+	if samples.len() >= MAX_PRICE_SAMPLES {
+		samples.remove(0);
+	}
+	
+	let reason = if moved_too_far(samples.iter().map(|s| s.spot_price), spot_price, config.max_price_move_percent) {
+		Some(format!("spot price moved more than {} per 1000", config.max_price_move_percent))
+	} else if moved_too_far(samples.iter().map(|s| s.reserve), reserve, config.max_reserve_move_percent) {
+		Some(format!("reserve moved more than {} per 1000", config.max_reserve_move_percent))
+	} else {
+		None
+	};
+	
+	samples.push(PriceSample {
+		height,
+		time,
+		spot_price,
+		reserve,
+	});
+	PRICE_SAMPLES.save(storage, &samples)?;
+	
+	if let Some(reason) = &reason {
+		BREAKER_TRIP.save(storage, &BreakerTrip {
+			reason: reason.clone(),
+			tripped_at: time,
+			resets_at: time + config.cooldown_seconds,
+		})?;
+	}
+	Ok(reason)
+}
+
+/// Whether value is more than max_move_percent per 1000 away from any earlier value
+fn moved_too_far(earlier: impl Iterator<Item = Uint128>, value: Uint128, max_move_percent: u32) -> bool {
+	if max_move_percent == 0 {
+		return false;
+	}
+	earlier
+	.filter(|e| !e.is_zero())
+	.any(|e| {
+		let diff = if value > e { value - e } else { e - value };
+		diff.multiply_ratio(1000u128, e) > Uint128::from(max_move_percent)
+	})
+}
+
+pub fn query_circuit_breaker_status(deps: Deps, env: Env) -> StdResult<CircuitBreakerStatusResponse> {
+	let config = CIRCUIT_BREAKER_CONFIG.may_load(deps.storage)?.unwrap_or_default();
+	let samples = PRICE_SAMPLES.may_load(deps.storage)?.unwrap_or_default();
+	// a trip past its cooldown is only cleared by the next trade
+	let trip = BREAKER_TRIP
+	.may_load(deps.storage)?
+	.filter(|t| env.block.time.seconds() < t.resets_at);
+	
+	Ok(CircuitBreakerStatusResponse {
+		tripped: trip.is_some(),
+		reason: trip.as_ref().map(|t| t.reason.clone()),
+		tripped_at: trip.as_ref().map(|t| t.tripped_at),
+		resets_at: trip.map(|t| t.resets_at),
+		max_price_move_percent: config.max_price_move_percent,
+		max_reserve_move_percent: config.max_reserve_move_percent,
+		window: config.window,
+		cooldown_seconds: config.cooldown_seconds,
+		samples: samples.len() as u32,
+	})
+}
+
+pub fn execute_update_circuitbreaker(
+deps: DepsMut,
+_env: Env,
+info: MessageInfo,
+max_price_move_percent: u32,
+max_reserve_move_percent: u32,
+window: BreakerWindow,
+cooldown_seconds: u64,
+) -> Result<Response, ContractError> {
+	
+	//Only owner or Pauser is authorized to proceed
+	assert_role(deps.storage, &info.sender, Role::Pauser)?;
+	
+	let config = CircuitBreakerConfig {
+		max_price_move_percent,
+		max_reserve_move_percent,
+		window,
+		cooldown_seconds,
+	};
+	// Save config back to contract storage.
+	CIRCUIT_BREAKER_CONFIG.save(deps.storage, &config)?;
+	
+	Ok(Response::default())
+}
+
+pub fn execute_reset_circuit_breaker(
+deps: DepsMut,
+env: Env,
+info: MessageInfo,
+) -> Result<Response, ContractError> {
+	
+	//Only owner or Pauser is authorized to proceed
+	assert_role(deps.storage, &info.sender, Role::Pauser)?;
+	
+	BREAKER_TRIP.remove(deps.storage);
+	PRICE_SAMPLES.remove(deps.storage);
+	
+	let event = Event::new("pause")
+	.add_attribute("pause_action", "circuit_breaker_reset")
+	.add_attribute("by", info.sender)
+	.add_attribute("height", env.block.height.to_string());
+	
+	Ok(Response::new()
+	.add_attribute("action", "reset_circuit_breaker")
+	.add_event(event))
+}
+
+/// The one gate every path that moves value passes
+fn assert_not_paused(storage: &dyn Storage, msg: &ExecuteMsg) -> Result<(), ContractError> {
+	let action = match msg {
+		ExecuteMsg::Buy { .. } => PauseAction::Buy,
+		ExecuteMsg::Burn { .. } => PauseAction::Burn,
+		ExecuteMsg::BurnFrom { .. } => PauseAction::BurnFrom,
+		ExecuteMsg::Transfer { .. } => PauseAction::Transfer,
+		ExecuteMsg::Send { .. } => PauseAction::Send,
+		ExecuteMsg::TransferFrom { .. } => PauseAction::TransferFrom,
+		ExecuteMsg::SendFrom { .. } => PauseAction::SendFrom,
+		ExecuteMsg::IncreaseAllowance { .. } => PauseAction::IncreaseAllowance,
+		ExecuteMsg::DecreaseAllowance { .. } => PauseAction::DecreaseAllowance,
+		ExecuteMsg::ClaimAffiliateRewards {} => PauseAction::ClaimAffiliateRewards,
+		ExecuteMsg::DistributeTaxes {} => PauseAction::DistributeTaxes,
+		_ => return Ok(()),
+	};
+	if PAUSED_ACTIONS.may_load(storage, action.as_str())?.unwrap_or(false) {
+		return Err(ContractError::ActionPaused { action: action.as_str().to_string() });
+	}
+	Ok(())
+}
+
+pub fn query_pause_status(deps: Deps) -> StdResult<PauseStatusResponse> {
+	let actions = PauseAction::ALL
+	.iter()
+	.map(|action| {
+		Ok(PauseFlag {
+			action: *action,
+			paused: PAUSED_ACTIONS.may_load(deps.storage, action.as_str())?.unwrap_or(false),
+		})
+	})
+	.collect::<StdResult<Vec<_>>>()?;
+	
+	Ok(PauseStatusResponse { actions })
+}
+
+/// Pausing is never timelocked. Every toggle leaves a pause event behind
+pub fn execute_set_pause(
+deps: DepsMut,
+env: Env,
+info: MessageInfo,
+action: PauseAction,
+paused: bool,
+) -> Result<Response, ContractError> {
+	
+	//Only owner or Pauser is authorized to proceed
+	assert_role(deps.storage, &info.sender, Role::Pauser)?;
+	
+	let event = toggle_pause(deps.storage, &env, &info.sender, action, paused)?;
+	
+	Ok(Response::new()
+	.add_attribute("action", "set_pause")
+	.add_event(event))
+}
+
+/// Sets the flag for action and returns the pause event recording the toggle
+fn toggle_pause(
+storage: &mut dyn Storage,
+env: &Env,
+by: &Addr,
+action: PauseAction,
+paused: bool,
+) -> StdResult<Event> {
+	let was_paused = PAUSED_ACTIONS.may_load(storage, action.as_str())?.unwrap_or(false);
+	if paused {
+		PAUSED_ACTIONS.save(storage, action.as_str(), &true)?;
+	} else {
+		PAUSED_ACTIONS.remove(storage, action.as_str());
+	}
+	
+	Ok(Event::new("pause")
+	.add_attribute("pause_action", action.as_str())
+	.add_attribute("paused", paused.to_string())
+	.add_attribute("was_paused", was_paused.to_string())
+	.add_attribute("by", by)
+	.add_attribute("height", env.block.height.to_string()))
+}
+
+pub fn execute_update_minter(
+deps: DepsMut,
+_env: Env,
+info: MessageInfo,
+new_minter: Option<String>,
+) -> Result<Response, ContractError> {
+	
+	let mut config = TOKEN_INFO
+	.may_load(deps.storage)?
+	.ok_or(ContractError::Unauthorized {})?;
+	
+	let mint = config.mint.as_ref().ok_or(ContractError::Unauthorized {})?;
+	//if mint.minter != info.sender {
+	assert_role(deps.storage, &info.sender, Role::MinterAdmin)?;
+	
+	let minter_data = new_minter
+	.map(|new_minter| deps.api.addr_validate(&new_minter))
+	.transpose()?
+	.map(|minter| MinterData {
+		minter,
+		cap: mint.cap,
+	});
+	
+	config.mint = minter_data;
+	
+	TOKEN_INFO.save(deps.storage, &config)?;
+	
+	Ok(Response::default()
+	.add_attribute("action", "update_minter")
+	.add_attribute(
+	"new_minter",
+	config
+	.mint
+	.map(|m| m.minter.into_string())
+	.unwrap_or_else(|| "None".to_string()),
+	))
+}
+
+pub fn query_reserve_health(deps: Deps, env: Env) -> StdResult<ReserveHealthResponse> {
+	let state = CURVE_STATE.load(deps.storage)?;
+	let accounts = ACCT_CONFIG.load(deps.storage)?;
+	let config = RESERVE_CONFIG.may_load(deps.storage)?.unwrap_or_default();
+	
+	// the stake account holds the reserve, bonded to validators or waiting to be.
+	// Without one nothing could have been staked
+	let (delegated, liquid) = match accounts.stake_acct {
+		Some(stake_addr) => (
+			deps.querier
+			.query_all_delegations(stake_addr.to_string())?
+			.into_iter()
+			.filter(|d| d.amount.denom == state.reserve_denom)
+			.fold(Uint128::zero(), |acc, d| acc + d.amount.amount),
+			deps.querier
+			.query_balance(stake_addr.to_string(), &state.reserve_denom)?
+			.amount,
+		),
+		None => (Uint128::zero(), Uint128::zero()),
+	};
+	// the liquid reserve on this contract counts as far as it is really there
+	let buffer = std::cmp::min(
+		state.liquid_reserve,
+		deps.querier.query_balance(env.contract.address, &state.reserve_denom)?.amount,
+	);
+	
+	let actual = delegated + liquid + buffer;
+	let shortfall = state.reserve.checked_sub(actual).unwrap_or_else(|_| Uint128::zero());
+	let surplus = actual.checked_sub(state.reserve).unwrap_or_else(|_| Uint128::zero());
+	let tolerance = state.reserve
+	.multiply_ratio(config.tolerance_percent, 1000u128);
+	
+	Ok(ReserveHealthResponse {
+		stored_reserve: state.reserve,
+		slashed_reserve: state.slashed_reserve,
+		delegated,
+		liquid,
+		buffer,
+		shortfall,
+		surplus,
+		healthy: shortfall <= tolerance,
+		tolerance_percent: config.tolerance_percent,
+		pause_sells: config.pause_sells,
+		mark_down: config.mark_down,
+	})
+}
+
+/// Anyone may sync, it only ever reacts to a reserve that has really gone missing. Marking
+/// the reserve down can't be undone, so a shortfall that may only be passing is left alone
+/// unless the owner or a Pauser syncs
+pub fn execute_sync_reserve(
+deps: DepsMut,
+env: Env,
+info: MessageInfo,
+) -> Result<Response, ContractError> {
+	nonpayable(&info)?;
+	
+	let health = query_reserve_health(deps.as_ref(), env.clone())?;
+	let mut res = Response::new()
+	.add_attribute("action", "sync_reserve")
+	.add_attribute("Stored Reserve: ", health.stored_reserve)
+	.add_attribute("Actual Reserve: ", health.delegated + health.liquid + health.buffer)
+	.add_attribute("Shortfall: ", health.shortfall);
+	
+	if health.healthy {
+		return Ok(res.add_attribute("Reserve Status: ", "healthy"));
+	}
+	res = res.add_attribute("Reserve Status: ", "shortfall");
+	
+	if health.pause_sells {
+		// only sells stop, buys and the trading status are left alone until a pauser resumes
+		for action in [PauseAction::Burn, PauseAction::BurnFrom] {
+			res = res.add_event(toggle_pause(deps.storage, &env, &info.sender, action, true)?);
+		}
+		res = res.add_attribute("Sells Paused: ", "true");
+	}
+	
+	if health.mark_down {
+		if assert_role(deps.storage, &info.sender, Role::Pauser).is_ok() {
+			let mut state = CURVE_STATE.load(deps.storage)?;
+			state.reserve -= health.shortfall;
+			state.slashed_reserve += health.shortfall;
+			CURVE_STATE.save(deps.storage, &state)?;
+			res = res.add_attribute("Reserve Marked Down: ", health.shortfall);
+		} else {
+			res = res.add_attribute("Reserve Marked Down: ", "needs owner or pauser");
+		}
+	}
+	
+	Ok(res)
+}
+
+pub fn execute_update_reserveconfig(
+deps: DepsMut,
+_env: Env,
+info: MessageInfo,
+tolerance_percent: u32,
+pause_sells: bool,
+mark_down: bool,
+) -> Result<Response, ContractError> {
+	
+	//Only owner or Pauser is authorized to proceed
+	assert_role(deps.storage, &info.sender, Role::Pauser)?;
+	
+	check_percent("tolerance_percent", tolerance_percent)?;
+	
+	let config = ReserveConfig {
+		tolerance_percent,
+		pause_sells,
+		mark_down,
+	};
+	// Save config back to contract storage.
+	RESERVE_CONFIG.save(deps.storage, &config)?;
+	
+	Ok(Response::default())
+}
+
+pub fn query_liquidityinfo(deps: Deps) -> StdResult<LiquidityInfoResponse> {
+	let state = CURVE_STATE.load(deps.storage)?;
+	let LiquidityConfig {
+		liquid_percent,
+		instant_fee_percent,
+	} = LIQUIDITY_CONFIG.may_load(deps.storage)?.unwrap_or_default();
+	
+	Ok(LiquidityInfoResponse {
+		liquid_percent,
+		instant_fee_percent,
+		liquid_reserve: state.liquid_reserve,
+		target_reserve: state.reserve.multiply_ratio(liquid_percent, 1000u128),
+	})
+}
+
+pub fn execute_update_liquidityconfig(
+deps: DepsMut,
+_env: Env,
+info: MessageInfo,
+liquid_percent: u32,
+instant_fee_percent: u32,
+) -> Result<Response, ContractError> {
+	
+	//Only owner or FeeManager is authorized to proceed
+	assert_role(deps.storage, &info.sender, Role::FeeManager)?;
+	
+	check_percent("liquid_percent", liquid_percent)?;
+	check_percent("instant_fee_percent", instant_fee_percent)?;
+	
+	let config = LiquidityConfig {
+		liquid_percent,
+		instant_fee_percent,
+	};
+	// Save config back to contract storage.
+	LIQUIDITY_CONFIG.save(deps.storage, &config)?;
+	
+	Ok(Response::default())
+}
+
+/// Splits tax over the tax recipients, the last one gets whatever the other shares leave
+fn split_tax(tax: Uint128, recipients: &[TaxRecipient]) -> Result<Vec<Uint128>, ContractError> {
+	if tax.is_zero() {
+		return Ok(vec![Uint128::zero(); recipients.len()]);
+	}
+	let (_, rest) = recipients.split_last().ok_or(ContractError::NoTaxRecipients {})?;
+	
+	let mut shares: Vec<Uint128> = rest
+	.iter()
+	.map(|r| tax.multiply_ratio(r.share_per_mille, 1000u128))
+	.collect();
+	let remainder = tax
+	.checked_sub(shares.iter().fold(Uint128::zero(), |acc, s| acc + *s))
+	.map_err(|_| ContractError::InvalidTaxSplit {
+		total: recipients.iter().map(|r| r.share_per_mille).sum(),
+	})?;
+	shares.push(remainder);
+	Ok(shares)
+}
+
+/// Builds the messages paying each tax share to its recipient and books them as paid.
+/// Empty shares are skipped, recipients without an address have their share burned
+fn pay_tax(
+storage: &mut dyn Storage,
+state: &mut CurveState,
+recipients: &[TaxRecipient],
+shares: &[Uint128],
+chain_tax: &ChainTaxConfig,
+) -> StdResult<Vec<CosmosMsg>> {
+	let mut messages = vec![];
+	
+	for (recipient, amount) in recipients.iter().zip(shares) {
+		if amount.is_zero() {
+			continue;
+		}
+		match &recipient.addr {
+			Some(addr) => {
+				messages.push(bank_send(addr, *amount, &state.reserve_denom, chain_tax));
+			}
+			None => {
+				messages.push(CosmosMsg::Bank(BankMsg::Burn {
+					amount: coins(amount.u128(), &state.reserve_denom),
+				}));
+				state.reserve_burned += *amount;
+			}
+		}
+		TAX_TOTALS.update(storage, &recipient.label, |total| -> StdResult<_> {
+			Ok(total.unwrap_or_default() + *amount)
+		})?;
+		state.tax_paid += *amount;
+	}
+	Ok(messages)
+}
+
+/// Adds each tax share to what its recipient is owed
+fn accrue_tax(
+storage: &mut dyn Storage,
+accrual: &Map<&str, Uint128>,
+recipients: &[TaxRecipient],
+shares: &[Uint128],
+) -> StdResult<()> {
+	for (recipient, amount) in recipients.iter().zip(shares) {
+		if amount.is_zero() {
+			continue;
+		}
+		accrual.update(storage, &recipient.label, |owed| -> StdResult<_> {
+			Ok(owed.unwrap_or_default() + *amount)
+		})?;
+	}
+	Ok(())
+}
+
+/// Send out of budget, leaving room on the contract for the chain tax on it
+fn bank_send(to: &Addr, budget: Uint128, denom: &str, chain_tax: &ChainTaxConfig) -> CosmosMsg {
+	CosmosMsg::Bank(BankMsg::Send {
+		to_address: to.to_string(),
+		amount: coins(chain_tax.deduct(budget).u128(), denom),
+	})
+}
+
+/// How much of reserve_in goes to the liquid reserve to bring it up to its target
+fn liquid_top_up(state: &CurveState, liquidity: &LiquidityConfig, reserve_in: Uint128) -> Uint128 {
+	let target = (state.reserve + reserve_in)
+	.multiply_ratio(liquidity.liquid_percent, 1000u128);
+	std::cmp::min(
+		reserve_in,
+		target.checked_sub(state.liquid_reserve).unwrap_or_else(|_| Uint128::zero()),
+	)
+}
+
+/// Chain burn tax from the Terra treasury, or the owner set fallback when the chain
+/// doesn't answer. The flag tells which one was used
+fn load_chain_tax(deps: Deps, denom: &str) -> StdResult<(ChainTaxConfig, bool)> {
+	let fallback = CHAIN_TAX_CONFIG.may_load(deps.storage)?.unwrap_or_default();
+	
+	let querier = TerraQuerier::new(&deps.querier);
+	match querier.query_tax_rate() {
+		Ok(rate) => {
+			// not every denom has a cap on chain
+			let cap = querier.query_tax_cap(denom).map(|c| c.cap).ok().or(fallback.cap);
+			Ok((ChainTaxConfig { rate: rate.rate, cap }, true))
+		}
+		Err(_) => Ok((fallback, false)),
+	}
+}
+
+pub fn query_chain_tax_info(deps: Deps) -> StdResult<ChainTaxInfoResponse> {
+	let state = CURVE_STATE.load(deps.storage)?;
+	let (tax, from_chain) = load_chain_tax(deps, &state.reserve_denom)?;
+	let fallback = CHAIN_TAX_CONFIG.may_load(deps.storage)?.unwrap_or_default();
+	
+	Ok(ChainTaxInfoResponse {
+		rate: tax.rate,
+		cap: tax.cap,
+		from_chain,
+		fallback_rate: fallback.rate,
+		fallback_cap: fallback.cap,
+	})
+}
+
+pub fn execute_update_chaintaxconfig(
+deps: DepsMut,
+_env: Env,
+info: MessageInfo,
+rate: Decimal,
+cap: Option<Uint128>,
+) -> Result<Response, ContractError> {
+	
+	//Only owner or FeeManager is authorized to proceed
+	assert_role(deps.storage, &info.sender, Role::FeeManager)?;
+	
+	let config = ChainTaxConfig {
+		rate,
+		cap,
+	};
+	// Save config back to contract storage.
+	CHAIN_TAX_CONFIG.save(deps.storage, &config)?;
+	
+	Ok(Response::default())
+}
+
+pub fn query_tax_accrual(deps: Deps) -> StdResult<TaxAccrualResponse> {
+	let state = CURVE_STATE.load(deps.storage)?;
+	let accrued = SELL_TAX_ACCRUED
+	.range(deps.storage, None, None, Order::Ascending)
+	.map(|item| {
+		let (label, amount) = item?;
+		Ok(TaxAmount {
+			label: String::from_utf8(label)?,
+			amount,
+		})
+	})
+	.collect::<StdResult<Vec<_>>>()?;
+	
+	Ok(TaxAccrualResponse {
+		tax_collected: state.tax_collected,
+		tax_paid: state.tax_paid,
+		accrued_total: accrued.iter().fold(Uint128::zero(), |acc, a| acc + a.amount),
+		accrued,
+	})
+}
+
+pub fn query_pending_taxes(deps: Deps, env: Env) -> StdResult<PendingTaxesResponse> {
+	let pending = TAX_PENDING
+	.range(deps.storage, None, None, Order::Ascending)
+	.map(|item| {
+		let (label, amount) = item?;
+		Ok(TaxAmount {
+			label: String::from_utf8(label)?,
+			amount,
+		})
+	})
+	.collect::<StdResult<Vec<_>>>()?;
+	let config = DISTRIBUTION_CONFIG.may_load(deps.storage)?.unwrap_or_default();
+	let next_distribution = match LAST_DISTRIBUTION.may_load(deps.storage)? {
+		Some(last) => last + config.min_interval,
+		None => env.block.time.seconds(),
+	};
+	
+	Ok(PendingTaxesResponse {
+		pending_total: pending.iter().fold(Uint128::zero(), |acc, a| acc + a.amount),
+		pending,
+		min_interval: config.min_interval,
+		next_distribution,
+	})
+}
+
+/// Anyone may distribute, it only pays out pending tax the contract actually holds
+/// outside of the liquid reserve and unclaimed affiliate rewards. Sell tax is only pending once returned
+pub fn execute_distribute_taxes(
+deps: DepsMut,
+env: Env,
+info: MessageInfo,
+) -> Result<Response, ContractError> {
+	nonpayable(&info)?;
+	
+	let now = env.block.time.seconds();
+	let config = DISTRIBUTION_CONFIG.may_load(deps.storage)?.unwrap_or_default();
+	if let Some(last) = LAST_DISTRIBUTION.may_load(deps.storage)? {
+		if now < last + config.min_interval {
+			return Err(ContractError::DistributeTooSoon { next: last + config.min_interval });
+		}
+	}
+	LAST_DISTRIBUTION.save(deps.storage, &now)?;
+	
+	let mut state = CURVE_STATE.load(deps.storage)?;
+	let recipients = TAX_RECIPIENTS.may_load(deps.storage)?.unwrap_or_default();
+	
+	let balance = deps.querier
+	.query_balance(env.contract.address, &state.reserve_denom)?
+	.amount;
+	let mut available = balance
+	.checked_sub(state.liquid_reserve + state.affiliate_owed)
+	.unwrap_or_else(|_| Uint128::zero());
+	
+	// pay the recipients in order until the funds on the contract run out
+	let mut shares = vec![Uint128::zero(); recipients.len()];
+	let mut still_pending = Uint128::zero();
+	for (recipient, share) in recipients.iter().zip(shares.iter_mut()) {
+		let owed = TAX_PENDING.may_load(deps.storage, &recipient.label)?.unwrap_or_default();
+		let amount = std::cmp::min(owed, available);
+		available -= amount;
+		*share += amount;
+		
+		if owed == amount {
+			TAX_PENDING.remove(deps.storage, &recipient.label);
+		} else {
+			TAX_PENDING.save(deps.storage, &recipient.label, &(owed - amount))?;
+			still_pending += owed - amount;
+		}
+	}
+	
+	let chain_tax = load_chain_tax(deps.as_ref(), &state.reserve_denom)?.0;
+	let messages = pay_tax(deps.storage, &mut state, &recipients, &shares, &chain_tax)?;
+	let paid = shares.iter().fold(Uint128::zero(), |acc, s| acc + *s);
+	
+	CURVE_STATE.save(deps.storage, &state)?;
+	
+	Ok(Response::new()
+	.add_messages(messages)
+	.add_attribute("action", "distribute_taxes")
+	.add_attribute("LUNC Tax Paid: ", paid)
+	.add_attribute("LUNC Tax Pending: ", still_pending))
+}
+
+/// Unbonding lands on the unstake account, not here. What it sends back with this is booked
+/// as pending, recipient by recipient, so DistributeTaxes never pays sell tax that hasn't arrived
+pub fn execute_return_sell_tax(
+deps: DepsMut,
+_env: Env,
+info: MessageInfo,
+) -> Result<Response, ContractError> {
+	let state = CURVE_STATE.load(deps.storage)?;
+	let payment = must_pay(&info, &state.reserve_denom)?;
+	
+	let accrued = SELL_TAX_ACCRUED
+	.range(deps.storage, None, None, Order::Ascending)
+	.map(|item| {
+		let (label, amount) = item?;
+		Ok((String::from_utf8(label)?, amount))
+	})
+	.collect::<StdResult<Vec<_>>>()?;
+	let accrued_total = accrued.iter().fold(Uint128::zero(), |acc, (_, a)| acc + *a);
+	if payment > accrued_total {
+		return Err(ContractError::SellTaxOverpaid { accrued: accrued_total });
+	}
+	
+	let mut left = payment;
+	for (label, owed) in accrued {
+		let amount = std::cmp::min(owed, left);
+		if amount.is_zero() {
+			break;
+		}
+		left -= amount;
+		if owed == amount {
+			SELL_TAX_ACCRUED.remove(deps.storage, &label);
+		} else {
+			SELL_TAX_ACCRUED.save(deps.storage, &label, &(owed - amount))?;
+		}
+		TAX_PENDING.update(deps.storage, &label, |pending| -> StdResult<_> {
+			Ok(pending.unwrap_or_default() + amount)
+		})?;
+	}
+	
+	Ok(Response::new()
+	.add_attribute("action", "return_sell_tax")
+	.add_attribute("LUNC Returned: ", payment)
+	.add_attribute("LUNC Tax Accrued: ", accrued_total - payment))
+}
+
+pub fn execute_update_distributionconfig(
+deps: DepsMut,
+_env: Env,
+info: MessageInfo,
+min_interval: u64,
+) -> Result<Response, ContractError> {
+	
+	//Only owner or FeeManager is authorized to proceed
+	assert_role(deps.storage, &info.sender, Role::FeeManager)?;
+	
+	DISTRIBUTION_CONFIG.save(deps.storage, &DistributionConfig { min_interval })?;
+	
+	Ok(Response::default())
+}
+
+pub fn execute_register_affiliate(
+deps: DepsMut,
+_env: Env,
+info: MessageInfo,
+code: String,
+) -> Result<Response, ContractError> {
+	nonpayable(&info)?;
+	assert_not_denylisted(deps.storage, "affiliate", &info.sender)?;
+	
+	let valid = (3..=32).contains(&code.len())
+	&& code.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+	if !valid {
+		return Err(ContractError::InvalidAffiliateCode { code });
+	}
+	if let Some(existing) = AFFILIATE_CODES.may_load(deps.storage, &info.sender)? {
+		return Err(ContractError::AlreadyAffiliate { code: existing });
+	}
+	if AFFILIATES.has(deps.storage, &code) {
+		return Err(ContractError::AffiliateCodeTaken { code });
+	}
+	if is_contract(&deps.querier, &info.sender)? {
+		return Err(ContractError::ContractReferral { code });
+	}
+	
+	AFFILIATES.save(deps.storage, &code, &Affiliate {
+		addr: info.sender.clone(),
+		volume: Uint128::zero(),
+		referrals: 0,
+		rewards_earned: Uint128::zero(),
+		rewards_claimed: Uint128::zero(),
+	})?;
+	AFFILIATE_CODES.save(deps.storage, &info.sender, &code)?;
+	
+	Ok(Response::new()
+	.add_attribute("action", "register_affiliate")
+	.add_attribute("affiliate", info.sender)
+	.add_attribute("code", code))
+}
+
+pub fn execute_claim_affiliate_rewards(
+deps: DepsMut,
+_env: Env,
+info: MessageInfo,
+) -> Result<Response, ContractError> {
+	nonpayable(&info)?;
+	assert_not_denylisted(deps.storage, "claimant", &info.sender)?;
+	
+	let code = AFFILIATE_CODES
+	.may_load(deps.storage, &info.sender)?
+	.ok_or(ContractError::NotAffiliate {})?;
+	let mut affiliate = AFFILIATES.load(deps.storage, &code)?;
+	let mut state = CURVE_STATE.load(deps.storage)?;
+	
+	let unclaimed = affiliate.rewards_earned - affiliate.rewards_claimed;
+	affiliate.rewards_claimed = affiliate.rewards_earned;
+	state.affiliate_owed = state.affiliate_owed
+	.checked_sub(unclaimed)
+	.map_err(StdError::overflow)?;
+	AFFILIATES.save(deps.storage, &code, &affiliate)?;
+	CURVE_STATE.save(deps.storage, &state)?;
+	
+	let mut res = Response::new()
+	.add_attribute("action", "claim_affiliate_rewards")
+	.add_attribute("code", code)
+	.add_attribute("Affiliate Reward: ", unclaimed);
+	if !unclaimed.is_zero() {
+		let chain_tax = load_chain_tax(deps.as_ref(), &state.reserve_denom)?.0;
+		res = res.add_message(bank_send(&info.sender, unclaimed, &state.reserve_denom, &chain_tax));
+	}
+	Ok(res)
+}
+
+pub fn query_affiliate_info(deps: Deps, code: String) -> StdResult<AffiliateInfoResponse> {
+	let affiliate = AFFILIATES.load(deps.storage, &code)?;
+	let params = PARAM_CONFIG.load(deps.storage)?;
+	let tiers = AFFILIATE_TIERS.may_load(deps.storage)?.unwrap_or_default();
+	Ok(affiliate_info(code, affiliate, &params, &tiers))
+}
+
+pub fn query_affiliate_tiers(deps: Deps) -> StdResult<AffiliateTiersResponse> {
+	let params = PARAM_CONFIG.load(deps.storage)?;
+	let tiers = AFFILIATE_TIERS.may_load(deps.storage)?.unwrap_or_default();
+	
+	Ok(AffiliateTiersResponse {
+		base_percent: params.affiliate_percent,
+		tiers,
+	})
+}
+
+pub fn execute_update_affiliate_tiers(
+deps: DepsMut,
+_env: Env,
+info: MessageInfo,
+tiers: Vec<AffiliateTier>,
+) -> Result<Response, ContractError> {
+	
+	//Only owner or FeeManager is authorized to proceed
+	assert_role(deps.storage, &info.sender, Role::FeeManager)?;
+	
+	let ceilings = PARAM_CEILINGS.may_load(deps.storage)?.unwrap_or_default();
+	let mut last_volume = Uint128::zero();
+	for tier in tiers.iter() {
+		if tier.min_volume <= last_volume {
+			return Err(ContractError::InvalidAffiliateTiers {});
+		}
+		last_volume = tier.min_volume;
+		check_percent("affiliate_percent", tier.affiliate_percent)?;
+		check_ceiling("affiliate_percent", tier.affiliate_percent, ceilings.max_affiliate_percent)?;
+	}
+	AFFILIATE_TIERS.save(deps.storage, &tiers)?;
+	
+	Ok(Response::new()
+	.add_attribute("action", "update_affiliate_tiers")
+	.add_attribute("tiers", tiers.len().to_string()))
+}
+
+/// The one place deciding which affiliate, if any, a buy is credited to.
+/// A code passed in has to be registered, not the buyer, not a contract and not denied or denylisted.
+/// The first code a buyer uses is kept, and with sticky_referrer it overrides the one passed
+/// in, as long as its affiliate is still allowed
+fn resolve_affiliate(
+deps: DepsMut,
+buyer: &Addr,
+code: &str,
+) -> Result<Option<(String, Affiliate)>, ContractError> {
+	let rules = AFFILIATE_RULES.may_load(deps.storage)?.unwrap_or_default();
+	let first = REFERRERS.may_load(deps.storage, buyer)?;
+	
+	if rules.sticky_referrer {
+		if let Some(first) = first {
+			let affiliate = AFFILIATES.load(deps.storage, &first)?;
+			if AFFILIATE_DENYLIST.has(deps.storage, &affiliate.addr)
+			|| COMPLIANCE_DENYLIST.has(deps.storage, &affiliate.addr) {
+				return Ok(None);
+			}
+			return Ok(Some((first, affiliate)));
+		}
+	}
+	if code.is_empty() {
+		return Ok(None);
+	}
+	
+	let affiliate = AFFILIATES
+	.may_load(deps.storage, code)?
+	.ok_or_else(|| ContractError::UnknownAffiliate { code: code.to_string() })?;
+	if &affiliate.addr == buyer {
+		return Err(ContractError::SelfReferral {});
+	}
+	if AFFILIATE_DENYLIST.has(deps.storage, &affiliate.addr) {
+		return Err(ContractError::AffiliateDenied { code: code.to_string() });
+	}
+	assert_not_denylisted(deps.storage, "affiliate", &affiliate.addr)?;
+	if is_contract(&deps.querier, &affiliate.addr)? {
+		return Err(ContractError::ContractReferral { code: code.to_string() });
+	}
+	
+	if first.is_none() {
+		REFERRERS.save(deps.storage, buyer, &code.to_string())?;
+	}
+	Ok(Some((code.to_string(), affiliate)))
+}
+
+/// Only contracts have contract info on chain. A querier that can't answer fails closed
+fn is_contract(querier: &QuerierWrapper, addr: &Addr) -> StdResult<bool> {
+	let request: QueryRequest<TerraQueryWrapper> = TerraQueryWrapper {
+		route: TerraRoute::Wasm,
+		query_data: TerraQuery::ContractInfo { contract_address: addr.to_string() },
+	}.into();
+	match querier.raw_query(&to_vec(&request)?) {
+		SystemResult::Err(system_err) => Err(StdError::generic_err(format!(
+			"Querier system error: {}",
+			system_err
+		))),
+		// the wasm module answers, it just knows no such contract
+		SystemResult::Ok(ContractResult::Err(_)) => Ok(false),
+		SystemResult::Ok(ContractResult::Ok(_)) => Ok(true),
+	}
+}
+
+pub fn query_affiliate_rules(deps: Deps, buyer: Option<String>) -> StdResult<AffiliateRulesResponse> {
+	let rules = AFFILIATE_RULES.may_load(deps.storage)?.unwrap_or_default();
+	let referrer = match buyer {
+		Some(buyer) => REFERRERS.may_load(deps.storage, &deps.api.addr_validate(&buyer)?)?,
+		None => None,
+	};
+	
+	Ok(AffiliateRulesResponse {
+		sticky_referrer: rules.sticky_referrer,
+		referrer,
+	})
+}
+
+pub fn execute_update_affiliaterules(
+deps: DepsMut,
+_env: Env,
+info: MessageInfo,
+sticky_referrer: bool,
+) -> Result<Response, ContractError> {
+	
+	//Only owner or FeeManager is authorized to proceed
+	assert_role(deps.storage, &info.sender, Role::FeeManager)?;
+	
+	AFFILIATE_RULES.save(deps.storage, &AffiliateRules { sticky_referrer })?;
+	
+	Ok(Response::default())
+}
+
+pub fn execute_update_affiliate_denylist(
+deps: DepsMut,
+_env: Env,
+info: MessageInfo,
+add: Vec<String>,
+remove: Vec<String>,
+) -> Result<Response, ContractError> {
+	
+	//Only owner or FeeManager is authorized to proceed
+	assert_role(deps.storage, &info.sender, Role::FeeManager)?;
+	
+	for addr in add.iter() {
+		AFFILIATE_DENYLIST.save(deps.storage, &deps.api.addr_validate(addr)?, &true)?;
+	}
+	for addr in remove.iter() {
+		AFFILIATE_DENYLIST.remove(deps.storage, &deps.api.addr_validate(addr)?);
+	}
+	
+	Ok(Response::new()
+	.add_attribute("action", "update_affiliate_denylist")
+	.add_attribute("added", add.len().to_string())
+	.add_attribute("removed", remove.len().to_string()))
+}
+
+pub fn query_affiliate_denylist(
+deps: Deps,
+start_after: Option<String>,
+limit: Option<u32>,
+) -> StdResult<AffiliateDenylistResponse> {
+	let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+	let start = start_after.map(Bound::exclusive);
+	
+	let denied = AFFILIATE_DENYLIST
+	.keys(deps.storage, start, None, Order::Ascending)
+	.take(limit)
+	.map(|addr| Ok(Addr::unchecked(String::from_utf8(addr)?)))
+	.collect::<StdResult<Vec<_>>>()?;
+	
+	Ok(AffiliateDenylistResponse { denied })
+}
+
+/// Highest holder tier reached by holder's balance and the buy tax after its discount, tier 0
+/// being the full base_percent
+fn holder_tax_percent(storage: &dyn Storage, holder: &Addr, base_percent: u32) -> StdResult<(u32, u32)> {
+	let tiers = HOLDER_TIERS.may_load(storage)?.unwrap_or_default();
+	let balance = BALANCES.may_load(storage, holder)?.unwrap_or_default();
+	Ok(tiers
+	.iter()
+	.enumerate()
+	.rev()
+	.find(|(_, t)| balance >= t.min_balance)
+	.map(|(i, t)| {
+		let discount = base_percent as u64 * t.discount_percent as u64 / 1000;
+		(i as u32 + 1, base_percent - discount as u32)
+	})
+	.unwrap_or((0, base_percent)))
+}
+
+/// Runs the numbers of execute_buy without touching state
+pub fn query_simulate_buy(
+deps: Deps,
+curve_fn: CurveFn,
+buyer: String,
+amount: Uint128,
+) -> StdResult<SimulateBuyResponse> {
+	let buyer = deps.api.addr_validate(&buyer)?;
+	let state = CURVE_STATE.load(deps.storage)?;
+	let params = PARAM_CONFIG.load(deps.storage)?;
+	let special = DEXFER_CONFIG.load(deps.storage)?;
+	let curve = curve_fn(state.clone().decimals);
+	let presale_price = Uint128::new(params.presale_price.into());
+	
+	// taxed in the same cases as execute_buy
+	let (holder_tier, tax_percent) = if presale_price.is_zero() && !special.is_manager(&buyer)
+	&& !special.is_tax_exempt(&buyer) {
+		holder_tax_percent(deps.storage, &buyer, params.tax_percent)?
+	} else {
+		(0, 0)
+	};
+	let tax_amount = amount.multiply_ratio(tax_percent, 1000u128);
+	let payment = amount - tax_amount;
+	
+	let minted = if presale_price.is_zero() || special.is_manager(&buyer) {
+		let mut reserve_in = payment;
+		if !special.is_manager(&buyer) {
+			let chain_tax = load_chain_tax(deps, &state.reserve_denom)?.0;
+			let liquidity = LIQUIDITY_CONFIG.may_load(deps.storage)?.unwrap_or_default();
+			let liquid_amt = liquid_top_up(&state, &liquidity, payment);
+			reserve_in = liquid_amt + chain_tax.deduct(payment - liquid_amt);
+		}
+		curve.supply(state.reserve + state.slashed_reserve + reserve_in)
+		.checked_sub(state.supply)
+		.map_err(StdError::overflow)?
+	} else {
+		let result = (payment.u128() * 100u128) / presale_price.u128();
+		Uint128::new(result * 1000000u128 / 100u128)
+	};
+	
+	Ok(SimulateBuyResponse {
+		holder_tier,
+		tax_percent,
+		tax_amount,
+		minted,
+	})
+}
+
+pub fn query_holder_tiers(deps: Deps, address: Option<String>) -> StdResult<HolderTiersResponse> {
+	let base_percent = PARAM_CONFIG.load(deps.storage)?.tax_percent;
+	let (balance, tier, tax_percent) = match address {
+		Some(address) => {
+			let holder = deps.api.addr_validate(&address)?;
+			let (tier, tax_percent) = holder_tax_percent(deps.storage, &holder, base_percent)?;
+			(Some(BALANCES.may_load(deps.storage, &holder)?.unwrap_or_default()), Some(tier), Some(tax_percent))
+		}
+		None => (None, None, None),
+	};
+	
+	Ok(HolderTiersResponse {
+		base_percent,
+		tiers: HOLDER_TIERS.may_load(deps.storage)?.unwrap_or_default(),
+		balance,
+		tier,
+		tax_percent,
+	})
+}
+
+pub fn execute_update_holder_tiers(
+deps: DepsMut,
+_env: Env,
+info: MessageInfo,
+tiers: Vec<HolderTier>,
+) -> Result<Response, ContractError> {
+	
+	//Only owner or FeeManager is authorized to proceed
+	assert_role(deps.storage, &info.sender, Role::FeeManager)?;
+	
+	let mut last_balance = Uint128::zero();
+	for tier in tiers.iter() {
+		if tier.min_balance <= last_balance {
+			return Err(ContractError::InvalidHolderTiers {});
+		}
+		last_balance = tier.min_balance;
+		check_percent("discount_percent", tier.discount_percent)?;
+	}
+	HOLDER_TIERS.save(deps.storage, &tiers)?;
+	
+	Ok(Response::new()
+	.add_attribute("action", "update_holder_tiers")
+	.add_attribute("tiers", tiers.len().to_string()))
+}
+
+/// Highest tier reached by volume, tier 0 being the base affiliate_percent
+fn tier_for(base_percent: u32, tiers: &[AffiliateTier], volume: Uint128) -> (u32, u32) {
+	tiers
+	.iter()
+	.enumerate()
+	.rev()
+	.find(|(_, t)| volume >= t.min_volume)
+	.map(|(i, t)| (i as u32 + 1, t.affiliate_percent))
+	.unwrap_or((0, base_percent))
+}
+
+// settings for pagination
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+pub fn query_affiliates(
+deps: Deps,
+start_after: Option<String>,
+limit: Option<u32>,
+) -> StdResult<AffiliatesResponse> {
+	let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+	let start = start_after.map(Bound::exclusive);
+	let params = PARAM_CONFIG.load(deps.storage)?;
+	let tiers = AFFILIATE_TIERS.may_load(deps.storage)?.unwrap_or_default();
+	
+	let affiliates = AFFILIATES
+	.range(deps.storage, start, None, Order::Ascending)
+	.take(limit)
+	.map(|item| {
+		let (code, affiliate) = item?;
+		Ok(affiliate_info(String::from_utf8(code)?, affiliate, &params, &tiers))
+	})
+	.collect::<StdResult<Vec<_>>>()?;
+	
+	Ok(AffiliatesResponse { affiliates })
+}
+
+fn affiliate_info(
+code: String,
+affiliate: Affiliate,
+params: &ParamConfig,
+tiers: &[AffiliateTier],
+) -> AffiliateInfoResponse {
+	let (tier, affiliate_percent) = tier_for(params.affiliate_percent, tiers, affiliate.volume);
+	AffiliateInfoResponse {
+		code,
+		addr: affiliate.addr,
+		volume: affiliate.volume,
+		referrals: affiliate.referrals,
+		rewards_earned: affiliate.rewards_earned,
+		rewards_claimed: affiliate.rewards_claimed,
+		tier,
+		affiliate_percent,
+	}
 }
 
 pub fn must_pay(info: &MessageInfo, denom: &str) -> Result<Uint128, PaymentError> {
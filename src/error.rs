@@ -1,4 +1,4 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{StdError, Uint128};
 use crate::contract::PaymentError;
 use thiserror::Error;
 
@@ -34,4 +34,115 @@ pub enum ContractError {
     #[error("Burning is Paused. Use DEX to Sell.")]
     BurnPaused {},
 
+    #[error("Tax shares add up to {total}, must be 1000")]
+    InvalidTaxSplit { total: u32 },
+
+    #[error("Tax recipient label '{label}' is empty or used twice")]
+    InvalidTaxLabel { label: String },
+
+    #[error("Tax recipient '{label}' still has sell tax accrued")]
+    TaxStillAccrued { label: String },
+
+    #[error("No tax recipients configured")]
+    NoTaxRecipients {},
+
+    #[error("Returned more than the {accrued} sell tax accrued")]
+    SellTaxOverpaid { accrued: Uint128 },
+
+    #[error("Taxes can't be distributed again before {next}")]
+    DistributeTooSoon { next: u64 },
+
+    #[error("Affiliate tier min_volume has to rise from tier to tier")]
+    InvalidAffiliateTiers {},
+
+    #[error("Holder tier min_balance has to rise from tier to tier")]
+    InvalidHolderTiers {},
+
+    #[error("Holding tax max_age_seconds has to rise from step to step")]
+    InvalidHoldingTaxSchedule {},
+
+    #[error("min_buy {min_buy} is above max_buy {max_buy}")]
+    InvalidBuyLimits { min_buy: Uint128, max_buy: Uint128 },
+
+    #[error("Invalid affiliate code {code}")]
+    InvalidAffiliateCode { code: String },
+
+    #[error("Affiliate code {code} is already taken")]
+    AffiliateCodeTaken { code: String },
+
+    #[error("Sender is already registered as affiliate {code}")]
+    AlreadyAffiliate { code: String },
+
+    #[error("No affiliate registered under {code}")]
+    UnknownAffiliate { code: String },
+
+    #[error("Buyer can't refer themselves")]
+    SelfReferral {},
+
+    #[error("Affiliate {code} is a contract")]
+    ContractReferral { code: String },
+
+    #[error("Affiliate {code} is barred from rewards")]
+    AffiliateDenied { code: String },
+
+    #[error("Sender is not a registered affiliate")]
+    NotAffiliate {},
+
+    #[error("Circuit breaker tripped, {reason}. Trading resumes at {resets_at}")]
+    CircuitBreakerTripped { reason: String, resets_at: u64 },
+
+    #[error("Buy is below the minimum of {min}")]
+    BuyTooSmall { min: Uint128 },
+
+    #[error("Buy is above the maximum of {max}")]
+    BuyTooLarge { max: Uint128 },
+
+    #[error("Buy is above the 24h cap per address, {remaining} left")]
+    AddressCapExceeded { remaining: Uint128 },
+
+    #[error("Buy is above the cap per block, {remaining} left")]
+    BlockCapExceeded { remaining: Uint128 },
+
+    #[error("Selling is on cooldown after a buy until {ends_at}")]
+    SellCooldown { ends_at: u64 },
+
+    #[error("Denylisted {rule}: {address} is blocked ({reason})")]
+    Denylisted { rule: String, address: String, reason: String },
+
+    #[error("{holder} would hold more than the cap of {max}")]
+    MaxBalanceExceeded { holder: String, max: Uint128 },
+
+    #[error("{action} is paused")]
+    ActionPaused { action: String },
+
+    #[error("Sender lacks the {role} role")]
+    MissingRole { role: String },
+
+    #[error("No pending change {id}")]
+    NoPendingChange { id: u64 },
+
+    #[error("Pending change {id} can't be applied before {ready_at}")]
+    ChangeNotReady { id: u64, ready_at: u64 },
+
+    #[error("Message is not a timelocked change")]
+    NotTimelocked {},
+
+    #[error("No ownership proposal pending")]
+    NoOwnershipProposal {},
+
+    #[error("Ownership proposal has expired")]
+    OwnershipProposalExpired {},
+
+    #[error("{account} is not set")]
+    AccountNotSet { account: String },
+
+    #[error("{field} is {value}, must be at most 1000")]
+    InvalidPercentage { field: String, value: u32 },
+
+    #[error("{field} is {value}, above its ceiling of {ceiling}")]
+    AboveCeiling { field: String, value: u32, ceiling: u32 },
+
+    #[error("Ceiling on {field} can't be raised above {ceiling}")]
+    CeilingRaised { field: String, ceiling: u32 },
+
 }
@@ -0,0 +1,23 @@
+use serde::de::DeserializeOwned;
+
+use cosmwasm_std::Pair;
+use cosmwasm_std::{from_slice, StdResult};
+
+pub(crate) fn deserialize_kv<T: DeserializeOwned>(kv: Pair) -> StdResult<Pair<T>> {
+    let (k, v) = kv;
+    let t = from_slice::<T>(&v)?;
+    Ok((k, t))
+}
+
+// TODO: add a check here that it is the real prefix?
+#[inline]
+pub(crate) fn trim(namespace: &[u8], key: &[u8]) -> Vec<u8> {
+    key[namespace.len()..].to_vec()
+}
+
+#[inline]
+pub(crate) fn concat(namespace: &[u8], key: &[u8]) -> Vec<u8> {
+    let mut k = namespace.to_vec();
+    k.extend_from_slice(key);
+    k
+}
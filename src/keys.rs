@@ -171,6 +171,14 @@ impl<'a> Prefixer<'a> for () {
     }
 }
 
+pub trait EmptyPrefix {
+    fn new() -> Self;
+}
+
+impl EmptyPrefix for () {
+    fn new() {}
+}
+
 impl<'a> Prefixer<'a> for &'a [u8] {
     fn prefix(&self) -> Vec<Key> {
         vec![Key::Ref(self)]
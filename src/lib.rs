@@ -8,6 +8,13 @@ pub mod helpers;
 pub mod keys;
 pub mod de;
 pub mod int_key;
+pub mod map;
+pub mod path;
+pub mod prefix;
+mod iter_helpers;
+
+#[cfg(test)]
+mod testing;
 
 
 
@@ -0,0 +1,120 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::marker::PhantomData;
+
+use crate::keys::{EmptyPrefix, Prefixer, PrimaryKey};
+use crate::path::Path;
+use crate::prefix::{Bound, Prefix};
+use cosmwasm_std::{Order, Pair, StdError, StdResult, Storage};
+
+/// Map stores typed values under keys built from a namespace and a `PrimaryKey`.
+/// It is the keyed counterpart of `Item`.
+#[derive(Debug, Clone)]
+pub struct Map<'a, K, T> {
+    namespace: &'a [u8],
+    // see https://doc.rust-lang.org/std/marker/struct.PhantomData.html#unused-type-parameters for why this is needed
+    key_type: PhantomData<K>,
+    data_type: PhantomData<T>,
+}
+
+impl<'a, K, T> Map<'a, K, T> {
+    pub const fn new(namespace: &'a str) -> Self {
+        Map {
+            namespace: namespace.as_bytes(),
+            data_type: PhantomData,
+            key_type: PhantomData,
+        }
+    }
+}
+
+impl<'a, K, T> Map<'a, K, T>
+where
+    T: Serialize + DeserializeOwned,
+    K: PrimaryKey<'a>,
+{
+    pub fn key(&self, k: K) -> Path<T> {
+        Path::new(self.namespace, &k.key())
+    }
+
+    pub fn prefix(&self, p: K::Prefix) -> Prefix<T> {
+        Prefix::new(self.namespace, &p.prefix())
+    }
+
+    pub fn sub_prefix(&self, p: K::SubPrefix) -> Prefix<T> {
+        Prefix::new(self.namespace, &p.prefix())
+    }
+
+    pub fn save(&self, store: &mut dyn Storage, k: K, data: &T) -> StdResult<()> {
+        self.key(k).save(store, data)
+    }
+
+    pub fn remove(&self, store: &mut dyn Storage, k: K) {
+        self.key(k).remove(store)
+    }
+
+    /// load will return an error if no data is set at the given key, or on parse error
+    pub fn load(&self, store: &dyn Storage, k: K) -> StdResult<T> {
+        self.key(k).load(store)
+    }
+
+    /// may_load will parse the data stored at the key if present, returns Ok(None) if no data there.
+    /// returns an error on issues parsing
+    pub fn may_load(&self, store: &dyn Storage, k: K) -> StdResult<Option<T>> {
+        self.key(k).may_load(store)
+    }
+
+    /// has returns true or false if any data is at this key, without parsing or interpreting the
+    /// contents.
+    pub fn has(&self, store: &dyn Storage, k: K) -> bool {
+        self.key(k).has(store)
+    }
+
+    /// Loads the data, perform the specified action, and store the result
+    /// in the database. This is shorthand for some common sequences, which may be useful.
+    ///
+    /// If the data exists, `action(Some(value))` is called. Otherwise `action(None)` is called.
+    pub fn update<A, E>(&self, store: &mut dyn Storage, k: K, action: A) -> Result<T, E>
+    where
+        A: FnOnce(Option<T>) -> Result<T, E>,
+        E: From<StdError>,
+    {
+        self.key(k).update(store, action)
+    }
+}
+
+// short-cut for simple keys, rather than .prefix(()).range(...)
+impl<'a, K, T> Map<'a, K, T>
+where
+    T: Serialize + DeserializeOwned,
+    K: PrimaryKey<'a>,
+    K::SubPrefix: EmptyPrefix,
+{
+    /// Raw keys come back as stored, without the namespace
+    pub fn range<'c>(
+        &self,
+        store: &'c dyn Storage,
+        min: Option<Bound>,
+        max: Option<Bound>,
+        order: Order,
+    ) -> Box<dyn Iterator<Item = StdResult<Pair<T>>> + 'c>
+    where
+        T: 'c,
+    {
+        self.sub_prefix(K::SubPrefix::new())
+            .range(store, min, max, order)
+    }
+
+    pub fn keys<'c>(
+        &self,
+        store: &'c dyn Storage,
+        min: Option<Bound>,
+        max: Option<Bound>,
+        order: Order,
+    ) -> Box<dyn Iterator<Item = Vec<u8>> + 'c>
+    where
+        T: 'c,
+    {
+        self.sub_prefix(K::SubPrefix::new())
+            .keys(store, min, max, order)
+    }
+}
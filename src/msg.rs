@@ -2,7 +2,7 @@ use cosmwasm_schema::{cw_serde, QueryResponses};
 use serde::{Deserialize, Serialize};
 use schemars::JsonSchema;
 use crate::curves::{decimal, Constant, Curve, DecimalPlaces, Linear, SquareRoot, Squared, Sigmoid};
-use cosmwasm_std::{Addr, Binary, Uint128}; //Decimal
+use cosmwasm_std::{Addr, Binary, Decimal, Uint128};
 use cw20::Expiration;
 use cw20::Logo;
 
@@ -30,6 +30,47 @@ pub struct InstantiateMsg {
     /// write a custom `instantiate`, and then dispatch `your::execute` -> `cw20_bonding::do_execute`
     /// with your custom curve as a parameter (and same with `query` -> `do_query`)
     pub curve_type: CurveType,
+
+    /// The settings below are optional and checked like their Update messages. Left out, the
+    /// contract starts untaxed in presale at 200, with no accounts, and only the creator trading
+    pub param_config: Option<InstantiateParamConfig>,
+    pub acct_config: Option<InstantiateAcctConfig>,
+    /// Needed together with a non-zero tax_percent
+    pub tax_recipients: Option<Vec<TaxRecipientMsg>>,
+    pub dexfer_config: Option<InstantiateDexferConfig>,
+    pub safety_config: Option<TradingStatus>,
+    pub marketing: Option<InstantiateMarketingInfo>,
+    pub logo: Option<Logo>,
+}
+
+#[cw_serde]
+pub struct InstantiateParamConfig {
+    pub affiliate_percent: u32,
+    pub tax_percent: u32,
+    pub presale_price: u32,
+}
+
+#[cw_serde]
+pub struct InstantiateAcctConfig {
+    pub presale_acct: Option<String>,
+    pub stake_acct: Option<String>,
+    pub unstake_acct: Option<String>,
+}
+
+#[cw_serde]
+pub struct InstantiateDexferConfig {
+    pub dexfer_manager: Option<String>,
+    pub deposit_percent: u32,
+    pub tax_exempt: Option<String>,
+    pub token_minter: String,
+}
+
+/// Marketing defaults to the creator
+#[cw_serde]
+pub struct InstantiateMarketingInfo {
+    pub project: Option<String>,
+    pub description: Option<String>,
+    pub marketing: Option<String>,
 }
 
 pub type CurveFn = Box<dyn Fn(DecimalPlaces) -> Box<dyn Curve>>;
@@ -89,16 +130,17 @@ impl CurveType {
 #[cw_serde]
 pub enum ExecuteMsg {
     /// Buy will attempt to purchase as many supply tokens as possible.
-    /// You must send only reserve tokens in that message. An affiliate
-    /// wallet address may be included for rewards, or pass empty String.
+    /// You must send only reserve tokens in that message. A registered
+    /// affiliate code may be included for rewards, or pass empty String.
     Buy {
 	affiliate: String,
     },
 
     /// Implements CW20. Transfer is a base message to move tokens to another account without triggering actions
     Transfer { recipient: String, amount: Uint128 },
-    /// Implements CW20. Burn is a base message to destroy tokens forever
-    Burn { amount: Uint128 },
+    /// Implements CW20. Burn is a base message to destroy tokens forever.
+    /// Set instant to be paid from the liquid reserve for an extra fee instead of unbonding
+    Burn { amount: Uint128, instant: Option<bool> },
     /// Implements CW20.  Send is a base message to transfer tokens to a contract and trigger an action
     /// on the receiving contract.
     Send {
@@ -137,8 +179,9 @@ pub enum ExecuteMsg {
         amount: Uint128,
         msg: Binary,
     },
-    /// Implements CW20 "approval" extension. Destroys tokens forever
-    BurnFrom { owner: String, amount: Uint128 },
+    /// Implements CW20 "approval" extension. Destroys tokens forever.
+    /// Set instant to be paid from the liquid reserve for an extra fee instead of unbonding
+    BurnFrom { owner: String, amount: Uint128, instant: Option<bool> },
     /// Only with the "marketing" extension. If authorized, updates marketing metadata.
     /// Setting None/null for any of these will leave it unchanged.
     /// Setting Some("") will clear this field on the contract storage
@@ -153,19 +196,102 @@ pub enum ExecuteMsg {
     /// If set as the "marketing" role on the contract, upload a new URL, SVG, or PNG for the token
     UploadLogo(Logo),
     /// Sets percentages for the tax as well as set the presale_price
-    UpdateParamConfig { yield_percent: u32, burn_percent: u32, social_percent: u32, expense_percent: u32,
-                        affiliate_percent: u32, tax_percent: u32, presale_price: u32, },
-    /// Set the accounts where funds will be deposited
-    UpdateAcctConfig { presale_acct: String, yield_acct: String, burn_acct: String, 
-			social_acct: String, expense_acct: String, stake_acct: String,
-			unstake_acct: String,},
-    /// Options for transferring BASE to an external DEX
-    UpdateDexferConfig { dexfer_manager: String, deposit_percent: u32, tax_exempt: String,
-			token_minter: String,},
+    UpdateParamConfig { affiliate_percent: u32, tax_percent: u32, presale_price: u32, },
+    /// Lowers the hard limits on tax_percent and affiliate_percent. They can never be raised again
+    LowerParamCeilings { max_tax_percent: u32, max_affiliate_percent: u32, },
+    /// Proposes a new owner, who has to AcceptOwnership before expiry. Replaces any earlier proposal
+    ProposeNewOwner { owner: String, expiry: Option<Expiration> },
+    /// The proposed owner takes over ownership
+    AcceptOwnership {},
+    /// Withdraws the pending ownership proposal
+    CancelOwnershipProposal {},
+    /// Leaves the contract without owner and takes away every role. This is irreversible
+    RenounceOwnership {},
+    /// Replaces the roles held by address, an empty list takes them all away
+    UpdateRoles { address: String, roles: Vec<Role> },
+    /// Sets the delay on changes to fees and where funds go. Raising it applies right away,
+    /// lowering it waits out the current delay. The safety knobs UpdateSafetyConfig,
+    /// UpdateBuyLimits, UpdateWhaleConfig, UpdateCircuitBreaker and UpdateReserveConfig are
+    /// exempt and always apply right away
+    UpdateTimelock { delay: u64 },
+    /// Applies a queued change once its delay has passed. Needs the same role as the change itself
+    ExecutePending { id: u64 },
+    /// Drops a queued change
+    CancelPending { id: u64 },
+    /// Set the accounts where funds will be deposited. Leave one out to keep it, pass an empty
+    /// address to unset it
+    UpdateAcctConfig { presale_acct: Option<String>, stake_acct: Option<String>,
+			unstake_acct: Option<String>,},
+    /// Replaces who receives the BASE tax. Shares must add up to 1000 and labels be unique.
+    /// A recipient without addr has its share burned
+    UpdateTaxRecipients { recipients: Vec<TaxRecipientMsg> },
+    /// Options for transferring BASE to an external DEX. Leave an account out to keep it, pass an
+    /// empty address to unset it
+    UpdateDexferConfig { dexfer_manager: Option<String>, deposit_percent: u32,
+			tax_exempt: Option<String>, token_minter: String,},
    /// Serves as an emergency switch
-    UpdateSafetyConfig { can_buy: String, can_sell: String, },
+    UpdateSafetyConfig { status: TradingStatus, },
+    /// Trips the circuit breaker when spot price or reserve moves more than the given per 1000
+    /// within window, stopping buys and sells for cooldown_seconds. 0 turns a check off
+    UpdateCircuitBreaker { max_price_move_percent: u32, max_reserve_move_percent: u32,
+			window: BreakerWindow, cooldown_seconds: u64, },
+    /// Lets trading resume before the circuit breaker cooldown is over
+    ResetCircuitBreaker {},
+    /// Sets the reserve a single buy has to stay within, what one address may buy per 24h and
+    /// what may be bought per block. Caps left out are off
+    UpdateBuyLimits { min_buy: Uint128, max_buy: Option<Uint128>, address_daily_cap: Option<Uint128>,
+			block_cap: Option<Uint128>, },
+    /// Caps the BASE one address may hold after a buy or transfer, per 1000 of supply. 0 turns it off.
+    /// The cap only applies once the supply reaches min_supply
+    UpdateWhaleConfig { max_balance_percent: u32, min_supply: Uint128, },
+    /// Exempts addresses such as the dexfer manager or a DEX pool from the balance cap, or ends it
+    UpdateWhaleWhitelist { add: Vec<String>, remove: Vec<String> },
+    /// Blocks addresses from buying, selling, transferring, approving and taking part as affiliates,
+    /// each with the reason shown when it is hit, or lifts the block
+    UpdateComplianceDenylist { add: Vec<DenylistEntry>, remove: Vec<String> },
+    /// Pauses or resumes one path that moves value, on top of the trading status
+    SetPause { action: PauseAction, paused: bool, },
    ///The current minter may set a new minter. Setting the minter to None is irreversible
     UpdateMinter { new_minter: Option<String> },
+    /// Compares the stored reserve with the delegated and liquid balance of the stake account.
+    /// Pauses sells (Burn and BurnFrom) and/or marks the reserve down per ReserveConfig when the
+    /// shortfall is too big. Anyone may pause sells this way, only the owner or a Pauser marks down
+    /// or resumes them with SetPause
+    SyncReserve {},
+    /// Sets the shortfall tolerance and what SyncReserve does when it is exceeded
+    UpdateReserveConfig { tolerance_percent: u32, pause_sells: bool, mark_down: bool, },
+    /// Sets how much of the reserve stays liquid and the fee for selling instantly out of it
+    UpdateLiquidityConfig { liquid_percent: u32, instant_fee_percent: u32, },
+    /// Pays the pending tax, returned sell tax included, to the tax recipients in one batch.
+    /// Anyone may call it, at most once per min_interval
+    DistributeTaxes {},
+    /// Returns unbonded sell tax to the contract, sent along in the reserve denom. It becomes
+    /// pending for the next DistributeTaxes. Can't be more than the sell tax accrued
+    ReturnSellTax {},
+    /// Replaces the affiliate tiers above the base affiliate_percent. min_volume has to rise
+    /// from tier to tier, each cut is bound by max_affiliate_percent
+    UpdateAffiliateTiers { tiers: Vec<AffiliateTier> },
+    /// Turns on or off crediting every buy to the first affiliate the buyer used
+    UpdateAffiliateRules { sticky_referrer: bool },
+    /// Bars affiliate addresses from rewards, or lifts the bar
+    UpdateAffiliateDenylist { add: Vec<String>, remove: Vec<String> },
+    /// Registers the sender as an affiliate under code, one code per address.
+    /// Codes are 3 to 32 letters, digits, '-' or '_'
+    RegisterAffiliate { code: String },
+    /// Pays the sender the affiliate rewards accrued on their code
+    ClaimAffiliateRewards {},
+    /// Sets the seconds that have to pass between two DistributeTaxes
+    UpdateDistributionConfig { min_interval: u64, },
+    /// Sets the chain burn tax used when the chain can't be queried for it
+    UpdateChainTaxConfig { rate: Decimal, cap: Option<Uint128>, },
+    /// Sets how long after a buy the buyer counts as exiting early. Early sells are refused, or
+    /// taxed at early_exit_tax_percent per 1000 when it is set. 0 seconds turns it off
+    UpdateCooldown { cooldown_seconds: u64, early_exit_tax_percent: Option<u32>, },
+    /// Replaces the sell tax by holding period. Steps are ordered by rising max_age_seconds,
+    /// tokens held longer than the last step pay tax_percent
+    UpdateHoldingTaxSchedule { steps: Vec<HoldingTaxStep> },
+    /// Replaces the buy tax discounts by BASE held. Tiers are ordered by rising min_balance
+    UpdateHolderTiers { tiers: Vec<HolderTier> },
   }
 
 #[cw_serde]
@@ -204,12 +330,82 @@ pub enum QueryMsg {
     /// Returns who can buy and sell on curve.
     #[returns(AcctInfoResponse)]
     AcctInfo{},
+    /// Lists the config changes waiting out the timelock.
+    #[returns(PendingChangesResponse)]
+    PendingChanges { start_after: Option<u64>, limit: Option<u32> },
+    /// Lists the addresses holding admin roles and their roles.
+    #[returns(RolesResponse)]
+    Roles { start_after: Option<String>, limit: Option<u32> },
+    /// Returns the owner and the owner proposed to take over, if any.
+    #[returns(PendingOwnerResponse)]
+    PendingOwner {},
     /// Returns who can buy and sell on curve.
     #[returns(DexferInfoResponse)]
     DexferInfo {},
     /// Returns who can buy and sell on curve.
     #[returns(SafetyInfoResponse)]
     SafetyInfo {},
+    /// Returns whether the circuit breaker tripped, why, and when it resets.
+    #[returns(CircuitBreakerStatusResponse)]
+    CircuitBreakerStatus {},
+    /// Returns the buy limits and how much of them address and the current block have left.
+    #[returns(LimitsResponse)]
+    Limits { address: String },
+    /// Returns when address last bought and whether a sell would still count as an early exit.
+    #[returns(CooldownResponse)]
+    Cooldown { address: String },
+    /// Returns the holding tax schedule and the sell tax address would pay now.
+    #[returns(HoldingTaxResponse)]
+    HoldingTax { address: Option<String> },
+    /// Returns the holder tiers and, for address, its tier and the buy tax it would pay now.
+    #[returns(HolderTiersResponse)]
+    HolderTiers { address: Option<String> },
+    /// Returns the buy tax, holder tier discount included, and the BASE buyer would get for
+    /// amount right now, without an affiliate.
+    #[returns(SimulateBuyResponse)]
+    SimulateBuy { buyer: String, amount: Uint128 },
+    /// Lists the addresses on the compliance denylist and why.
+    #[returns(ComplianceDenylistResponse)]
+    ComplianceDenylist { start_after: Option<String>, limit: Option<u32> },
+    /// Returns the balance cap and lists the addresses exempt from it.
+    #[returns(WhaleInfoResponse)]
+    WhaleInfo { start_after: Option<String>, limit: Option<u32> },
+    /// Returns which paths that move value are paused.
+    #[returns(PauseStatusResponse)]
+    PauseStatus {},
+    /// Returns the stored reserve next to the actual balance backing it.
+    #[returns(ReserveHealthResponse)]
+    ReserveHealth {},
+    /// Returns the liquid reserve available for instant sells.
+    #[returns(LiquidityInfoResponse)]
+    LiquidityInfo {},
+    /// Returns lifetime tax collected, what was paid out and what is still accrued.
+    #[returns(TaxAccrualResponse)]
+    TaxAccrual {},
+    /// Returns the chain burn tax applied to sends, and where it was read from.
+    #[returns(ChainTaxInfoResponse)]
+    ChainTaxInfo {},
+    /// Returns the tax recipients and the tax each has been paid since start.
+    #[returns(TaxRecipientsResponse)]
+    TaxRecipients {},
+    /// Returns the tax held on the contract for each recipient and when it can next be distributed.
+    #[returns(PendingTaxesResponse)]
+    PendingTaxes {},
+    /// Returns the volume and rewards of the affiliate registered under code.
+    #[returns(AffiliateInfoResponse)]
+    AffiliateInfo { code: String },
+    /// Returns the base affiliate cut and the tiers above it.
+    #[returns(AffiliateTiersResponse)]
+    AffiliateTiers {},
+    /// Returns the owner rules on affiliates and the affiliate a buyer is bound to, if any.
+    #[returns(AffiliateRulesResponse)]
+    AffiliateRules { buyer: Option<String> },
+    /// Lists the affiliate addresses barred from rewards.
+    #[returns(AffiliateDenylistResponse)]
+    AffiliateDenylist { start_after: Option<String>, limit: Option<u32> },
+    /// Lists the registered affiliates by code.
+    #[returns(AffiliatesResponse)]
+    Affiliates { start_after: Option<String>, limit: Option<u32> },
 
 }
 
@@ -222,42 +418,432 @@ pub struct CurveInfoResponse {
     pub spot_price: Uint128,
     pub reserve_denom: String,
     pub tax_collected: Uint128,
+    // reserve denom destroyed by burning tax recipients
+    pub reserve_burned: Uint128,
 }
 
 #[cw_serde]
 pub struct ParamInfoResponse {
-    pub yield_percent: u32,
-    pub burn_percent: u32,
-    pub social_percent: u32,
-    pub expense_percent: u32,
     pub affiliate_percent: u32,
     pub tax_percent: u32,
     pub presale_price: u32,
+    pub max_tax_percent: u32,
+    pub max_affiliate_percent: u32,
 }
 
 #[cw_serde]
 pub struct AcctInfoResponse {
-    pub owner: Addr,
-    pub presale_acct: String,
-    pub yield_acct: String,
-    pub burn_acct: String,
-    pub social_acct: String,
-    pub expense_acct: String,
-    pub stake_acct: String,
-    pub unstake_acct: String,
+    pub owner: Option<Addr>,
+    pub presale_acct: Option<Addr>,
+    pub stake_acct: Option<Addr>,
+    pub unstake_acct: Option<Addr>,
+}
+
+/// Who may trade on the curve
+#[cw_serde]
+pub enum TradingStatus {
+    /// anyone may buy and sell
+    Open,
+    /// nobody may buy or sell
+    Paused,
+    /// only these addresses may buy and sell
+    Allowlist(Vec<Addr>),
+    /// anyone may sell, nobody may buy
+    SellOnly,
+}
+
+impl TradingStatus {
+    pub fn can_buy(&self, addr: &Addr) -> bool {
+        match self {
+            TradingStatus::Open => true,
+            TradingStatus::Allowlist(allowed) => allowed.contains(addr),
+            TradingStatus::Paused | TradingStatus::SellOnly => false,
+        }
+    }
+
+    pub fn can_sell(&self, addr: &Addr) -> bool {
+        match self {
+            TradingStatus::Open | TradingStatus::SellOnly => true,
+            TradingStatus::Allowlist(allowed) => allowed.contains(addr),
+            TradingStatus::Paused => false,
+        }
+    }
+}
+
+/// How far back the circuit breaker compares prices
+#[cw_serde]
+pub enum BreakerWindow {
+    Blocks(u64),
+    Seconds(u64),
+}
+
+/// Paths that move value, each can be paused on its own. Payouts of what is already owed,
+/// affiliate rewards and the tax, can be held back too while something is looked into
+#[cw_serde]
+#[derive(Copy)]
+pub enum PauseAction {
+    Buy,
+    Burn,
+    BurnFrom,
+    Transfer,
+    Send,
+    TransferFrom,
+    SendFrom,
+    IncreaseAllowance,
+    DecreaseAllowance,
+    ClaimAffiliateRewards,
+    DistributeTaxes,
+}
+
+impl PauseAction {
+    pub const ALL: [PauseAction; 11] = [
+        PauseAction::Buy,
+        PauseAction::Burn,
+        PauseAction::BurnFrom,
+        PauseAction::Transfer,
+        PauseAction::Send,
+        PauseAction::TransferFrom,
+        PauseAction::SendFrom,
+        PauseAction::IncreaseAllowance,
+        PauseAction::DecreaseAllowance,
+        PauseAction::ClaimAffiliateRewards,
+        PauseAction::DistributeTaxes,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PauseAction::Buy => "buy",
+            PauseAction::Burn => "burn",
+            PauseAction::BurnFrom => "burn_from",
+            PauseAction::Transfer => "transfer",
+            PauseAction::Send => "send",
+            PauseAction::TransferFrom => "transfer_from",
+            PauseAction::SendFrom => "send_from",
+            PauseAction::IncreaseAllowance => "increase_allowance",
+            PauseAction::DecreaseAllowance => "decrease_allowance",
+            PauseAction::ClaimAffiliateRewards => "claim_affiliate_rewards",
+            PauseAction::DistributeTaxes => "distribute_taxes",
+        }
+    }
+}
+
+#[cw_serde]
+pub struct CircuitBreakerStatusResponse {
+    pub tripped: bool,
+    pub reason: Option<String>,
+    pub tripped_at: Option<u64>,
+    // block time in seconds when trading resumes
+    pub resets_at: Option<u64>,
+    pub max_price_move_percent: u32,
+    pub max_reserve_move_percent: u32,
+    pub window: BreakerWindow,
+    pub cooldown_seconds: u64,
+    // trades in the current window
+    pub samples: u32,
+}
+
+#[cw_serde]
+pub struct LimitsResponse {
+    pub min_buy: Uint128,
+    pub max_buy: Option<Uint128>,
+    pub address_daily_cap: Option<Uint128>,
+    pub address_used: Uint128,
+    pub address_remaining: Option<Uint128>,
+    // block time in seconds when the address starts a new 24h window
+    pub address_resets_at: Option<u64>,
+    pub block_cap: Option<Uint128>,
+    pub block_used: Uint128,
+    pub block_remaining: Option<Uint128>,
+}
+
+#[cw_serde]
+pub struct CooldownResponse {
+    pub cooldown_seconds: u64,
+    pub early_exit_tax_percent: Option<u32>,
+    // block time in seconds of the last buy
+    pub last_buy: Option<u64>,
+    // block time in seconds when selling stops being an early exit, None when not in cooldown
+    pub ends_at: Option<u64>,
+}
+
+#[cw_serde]
+pub struct DenylistEntry {
+    pub address: String,
+    pub reason: String,
+}
+
+#[cw_serde]
+pub struct DenylistEntryInfo {
+    pub address: Addr,
+    pub reason: String,
+}
+
+#[cw_serde]
+pub struct ComplianceDenylistResponse {
+    pub entries: Vec<DenylistEntryInfo>,
+}
+
+#[cw_serde]
+pub struct WhaleInfoResponse {
+    pub max_balance_percent: u32,
+    pub min_supply: Uint128,
+    // cap at the current supply, None when off
+    pub max_balance: Option<Uint128>,
+    pub whitelist: Vec<Addr>,
+}
+
+#[cw_serde]
+pub struct PauseFlag {
+    pub action: PauseAction,
+    pub paused: bool,
+}
+
+#[cw_serde]
+pub struct PauseStatusResponse {
+    pub actions: Vec<PauseFlag>,
+}
+
+/// Admin roles the owner hands out. The owner holds every role
+#[cw_serde]
+pub enum Role {
+    /// tax, affiliate and fee settings, and the buy limits
+    FeeManager,
+    /// accounts funds are routed to
+    AccountManager,
+    /// buy and sell switches and reserve safety
+    Pauser,
+    /// who may mint
+    MinterAdmin,
+}
+
+impl Role {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::FeeManager => "fee_manager",
+            Role::AccountManager => "account_manager",
+            Role::Pauser => "pauser",
+            Role::MinterAdmin => "minter_admin",
+        }
+    }
+}
+
+#[cw_serde]
+pub struct RoleAssignment {
+    pub address: Addr,
+    pub roles: Vec<Role>,
+}
+
+#[cw_serde]
+pub struct PendingChangeInfo {
+    pub id: u64,
+    pub msg: ExecuteMsg,
+    pub proposer: Addr,
+    // block time in seconds from which ExecutePending applies it
+    pub ready_at: u64,
+}
+
+#[cw_serde]
+pub struct PendingChangesResponse {
+    pub delay: u64,
+    pub changes: Vec<PendingChangeInfo>,
+}
+
+#[cw_serde]
+pub struct RolesResponse {
+    pub assignments: Vec<RoleAssignment>,
+}
+
+/// Affiliate cut once an affiliate's lifetime volume reaches min_volume
+#[cw_serde]
+pub struct AffiliateTier {
+    pub min_volume: Uint128,
+    pub affiliate_percent: u32,
+}
+
+/// Buy tax discount, per 1000 of tax_percent, once a buyer holds min_balance BASE
+#[cw_serde]
+pub struct HolderTier {
+    pub min_balance: Uint128,
+    pub discount_percent: u32,
+}
+
+/// Sell tax for tokens held less than max_age_seconds
+#[cw_serde]
+pub struct HoldingTaxStep {
+    pub max_age_seconds: u64,
+    pub tax_percent: u32,
+}
+
+#[cw_serde]
+pub struct PendingOwnerResponse {
+    pub owner: Option<Addr>,
+    pub pending_owner: Option<Addr>,
+    pub expiry: Option<Expiration>,
+}
+
+#[cw_serde]
+pub struct TaxRecipientMsg {
+    // leave out to burn the share
+    pub addr: Option<String>,
+    pub share_per_mille: u32,
+    pub label: String,
 }
 
 #[cw_serde]
 pub struct DexferInfoResponse {
-    pub dexfer_manager: String,
+    pub dexfer_manager: Option<Addr>,
     pub deposit_percent: u32,
-    pub tax_exempt: String,
+    pub tax_exempt: Option<Addr>,
     pub token_minter: String,
 }
 
 #[cw_serde]
 pub struct SafetyInfoResponse {
-    pub can_buy: String,
-    pub can_sell: String,
+    pub status: TradingStatus,
+}
+
+#[cw_serde]
+pub struct ReserveHealthResponse {
+    // reserve the curve currently promises
+    pub stored_reserve: Uint128,
+    // reserve already written down by SyncReserve
+    pub slashed_reserve: Uint128,
+    // reserve denom delegated by the stake account
+    pub delegated: Uint128,
+    // reserve denom held liquid by the stake account
+    pub liquid: Uint128,
+    // liquid reserve held by this contract for instant sells
+    pub buffer: Uint128,
+    pub shortfall: Uint128,
+    pub surplus: Uint128,
+    // false when the shortfall is above tolerance_percent
+    pub healthy: bool,
+    pub tolerance_percent: u32,
+    pub pause_sells: bool,
+    pub mark_down: bool,
+}
+
+#[cw_serde]
+pub struct LiquidityInfoResponse {
+    pub liquid_percent: u32,
+    pub instant_fee_percent: u32,
+    // liquid reserve currently held by the contract
+    pub liquid_reserve: Uint128,
+    // liquid reserve wanted at the current reserve
+    pub target_reserve: Uint128,
+}
+
+#[cw_serde]
+pub struct TaxAmount {
+    pub label: String,
+    pub amount: Uint128,
+}
+
+#[cw_serde]
+pub struct TaxAccrualResponse {
+    pub tax_collected: Uint128,
+    pub tax_paid: Uint128,
+    // sell tax waiting to be unbonded and returned, per tax recipient
+    pub accrued: Vec<TaxAmount>,
+    pub accrued_total: Uint128,
+}
+
+#[cw_serde]
+pub struct PendingTaxesResponse {
+    // tax held on the contract, per tax recipient
+    pub pending: Vec<TaxAmount>,
+    pub pending_total: Uint128,
+    pub min_interval: u64,
+    // block time in seconds from which DistributeTaxes can be called
+    pub next_distribution: u64,
+}
+
+#[cw_serde]
+pub struct AffiliateInfoResponse {
+    pub code: String,
+    pub addr: Addr,
+    pub volume: Uint128,
+    pub referrals: u64,
+    pub rewards_earned: Uint128,
+    pub rewards_claimed: Uint128,
+    // tier the next referral is paid at, 0 being the base affiliate_percent
+    pub tier: u32,
+    pub affiliate_percent: u32,
+}
+
+#[cw_serde]
+pub struct AffiliateTiersResponse {
+    pub base_percent: u32,
+    pub tiers: Vec<AffiliateTier>,
+}
+
+#[cw_serde]
+pub struct HolderTiersResponse {
+    pub base_percent: u32,
+    pub tiers: Vec<HolderTier>,
+    pub balance: Option<Uint128>,
+    // 0 is no discount
+    pub tier: Option<u32>,
+    pub tax_percent: Option<u32>,
+}
+
+#[cw_serde]
+pub struct SimulateBuyResponse {
+    // 0 is no discount
+    pub holder_tier: u32,
+    // per 1000 of the amount, after the holder tier discount
+    pub tax_percent: u32,
+    pub tax_amount: Uint128,
+    pub minted: Uint128,
+}
 
+#[cw_serde]
+pub struct HoldingTaxResponse {
+    pub base_percent: u32,
+    pub steps: Vec<HoldingTaxStep>,
+    // weighted average block time in seconds the address's tokens were acquired at
+    pub acquired_at: Option<u64>,
+    pub tax_percent: Option<u32>,
+}
+
+#[cw_serde]
+pub struct AffiliateRulesResponse {
+    pub sticky_referrer: bool,
+    // first affiliate code the buyer used
+    pub referrer: Option<String>,
+}
+
+#[cw_serde]
+pub struct AffiliateDenylistResponse {
+    pub denied: Vec<Addr>,
+}
+
+#[cw_serde]
+pub struct AffiliatesResponse {
+    pub affiliates: Vec<AffiliateInfoResponse>,
+}
+
+#[cw_serde]
+pub struct ChainTaxInfoResponse {
+    // rate and cap applied right now
+    pub rate: Decimal,
+    pub cap: Option<Uint128>,
+    // true when read from the chain, false when the fallback is used
+    pub from_chain: bool,
+    pub fallback_rate: Decimal,
+    pub fallback_cap: Option<Uint128>,
+}
+
+#[cw_serde]
+pub struct TaxRecipientInfo {
+    pub addr: Option<Addr>,
+    pub share_per_mille: u32,
+    pub label: String,
+    // tax paid to this recipient since start
+    pub total_paid: Uint128,
+}
+
+#[cw_serde]
+pub struct TaxRecipientsResponse {
+    pub recipients: Vec<TaxRecipientInfo>,
 }
@@ -1,10 +1,12 @@
 use cosmwasm_schema::cw_serde;
 
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, Decimal, Fraction, Uint128};
+use crate::map::Map;
+use cw20::Expiration;
 use crate::item::Item;
 
 use crate::curves::DecimalPlaces;
-use crate::msg::CurveType;
+use crate::msg::{AffiliateTier, BreakerWindow, CurveType, ExecuteMsg, HolderTier, HoldingTaxStep, Role, TradingStatus};
 
 /// Supply is dynamic and tracks the current supply of staked and ERC20 tokens.
 #[cw_serde]
@@ -22,6 +24,26 @@ pub struct CurveState {
 
     // How much tax collected since start
     pub tax_collected: Uint128,
+
+    // reserve written down after a slashing, still priced into the curve
+    #[serde(default)]
+    pub slashed_reserve: Uint128,
+
+    // part of the reserve kept liquid on the contract for instant sells
+    #[serde(default)]
+    pub liquid_reserve: Uint128,
+
+    // How much of tax_collected has been sent to the tax accounts
+    #[serde(default)]
+    pub tax_paid: Uint128,
+
+    // How much reserve denom the burn share has destroyed since start
+    #[serde(default)]
+    pub reserve_burned: Uint128,
+
+    // affiliate rewards held on the contract until claimed
+    #[serde(default)]
+    pub affiliate_owed: Uint128,
 }
 
 impl CurveState {
@@ -32,13 +54,97 @@ impl CurveState {
             reserve_denom,
             decimals,
             tax_collected: Uint128::zero(),
+            slashed_reserve: Uint128::zero(),
+            liquid_reserve: Uint128::zero(),
+            tax_paid: Uint128::zero(),
+            reserve_burned: Uint128::zero(),
+            affiliate_owed: Uint128::zero(),
         }
     }
 }
 
 #[cw_serde]
 pub struct ParamConfig {
-    pub yield_percent: u32,    
+    pub affiliate_percent: u32,
+    pub tax_percent: u32,
+    pub presale_price: u32,
+}
+
+/// Hard limits on ParamConfig, they can only ever be lowered
+#[cw_serde]
+pub struct ParamCeilings {
+    pub max_tax_percent: u32,
+    pub max_affiliate_percent: u32,
+}
+
+impl Default for ParamCeilings {
+    fn default() -> Self {
+        ParamCeilings {
+            max_tax_percent: 1000,
+            max_affiliate_percent: 1000,
+        }
+    }
+}
+
+#[cw_serde]
+pub struct AcctConfig {
+    // None once ownership is renounced
+    pub owner: Option<Addr>,
+    // None until the owner sets it
+    pub presale_acct: Option<Addr>,
+    pub stake_acct: Option<Addr>,
+    pub unstake_acct: Option<Addr>,
+}
+
+/// AcctConfig while accounts were unchecked strings defaulting to "none", only read by migrate
+#[cw_serde]
+pub struct UncheckedAcctConfig {
+    pub owner: Option<Addr>,
+    pub presale_acct: Option<String>,
+    pub stake_acct: Option<String>,
+    pub unstake_acct: Option<String>,
+}
+
+/// Owner proposed by the current one, waiting for them to accept
+#[cw_serde]
+pub struct OwnershipProposal {
+    pub owner: Addr,
+    pub expiry: Option<Expiration>,
+}
+
+/// Delay on changes to fees and where funds go
+#[cw_serde]
+#[derive(Default)]
+pub struct TimelockConfig {
+    // seconds between queueing a change and applying it, 0 applies right away
+    pub delay: u64,
+}
+
+/// Config change waiting out the timelock
+#[cw_serde]
+pub struct PendingChange {
+    pub id: u64,
+    pub msg: ExecuteMsg,
+    pub proposer: Addr,
+    // block time in seconds from which it can be applied
+    pub ready_at: u64,
+}
+
+/// One beneficiary of the BASE tax
+#[cw_serde]
+pub struct TaxRecipient {
+    // None destroys the share with BankMsg::Burn
+    pub addr: Option<Addr>,
+    // share of the tax, all recipients add up to 1000
+    pub share_per_mille: u32,
+    // unique name, also keys the lifetime totals
+    pub label: String,
+}
+
+/// ParamConfig before the N-way tax split, only read by migrate
+#[cw_serde]
+pub struct LegacyParamConfig {
+    pub yield_percent: u32,
     pub burn_percent: u32,
     pub social_percent: u32,
     pub expense_percent: u32,
@@ -47,8 +153,9 @@ pub struct ParamConfig {
     pub presale_price: u32,
 }
 
+/// AcctConfig before the N-way tax split, only read by migrate
 #[cw_serde]
-pub struct AcctConfig {
+pub struct LegacyAcctConfig {
     pub owner: Addr,
     pub presale_acct: String,
     pub yield_acct: String,
@@ -61,18 +168,218 @@ pub struct AcctConfig {
 
 #[cw_serde]
 pub struct DexferConfig {
-    pub dexfer_manager: String,
+    pub dexfer_manager: Option<Addr>,
     pub deposit_percent: u32, 
-    pub tax_exempt: String,
+    pub tax_exempt: Option<Addr>,
+    pub token_minter: String,
+}
+
+impl DexferConfig {
+    pub fn is_manager(&self, addr: &Addr) -> bool {
+        self.dexfer_manager.as_ref() == Some(addr)
+    }
+
+    pub fn is_tax_exempt(&self, addr: &Addr) -> bool {
+        self.tax_exempt.as_ref() == Some(addr)
+    }
+}
+
+/// DexferConfig while accounts were unchecked strings defaulting to "none", only read by migrate
+#[cw_serde]
+pub struct UncheckedDexferConfig {
+    pub dexfer_manager: Option<String>,
+    pub deposit_percent: u32,
+    pub tax_exempt: Option<String>,
     pub token_minter: String,
 }
 
 #[cw_serde]
 pub struct SafetyConfig {
-    pub can_buy: String,    
+    pub status: TradingStatus,
+}
+
+/// SafetyConfig while "1" meant open, an address only that address and anything else paused,
+/// only read by migrate
+#[cw_serde]
+pub struct LegacySafetyConfig {
+    pub can_buy: String,
     pub can_sell: String,
 }
 
+#[cw_serde]
+#[derive(Default)]
+pub struct ReserveConfig {
+    // shortfall tolerated before acting, per 1000 of the stored reserve
+    pub tolerance_percent: u32,
+    // pause Burn and BurnFrom when the shortfall is above tolerance
+    pub pause_sells: bool,
+    // write the stored reserve down to the actual balance when the shortfall is above tolerance
+    pub mark_down: bool,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct LiquidityConfig {
+    // share of the reserve kept on the contract instead of staked, per 1000
+    pub liquid_percent: u32,
+    // extra fee for an instant sell, per 1000 of the net released amount
+    pub instant_fee_percent: u32,
+}
+
+/// Chain burn tax charged on every send leaving the contract
+#[cw_serde]
+pub struct ChainTaxConfig {
+    pub rate: Decimal,
+    // most tax charged on a single send, None if uncapped
+    pub cap: Option<Uint128>,
+}
+
+impl Default for ChainTaxConfig {
+    fn default() -> Self {
+        // Terra Classic burn tax at launch
+        ChainTaxConfig {
+            rate: Decimal::permille(5),
+            cap: None,
+        }
+    }
+}
+
+impl ChainTaxConfig {
+    /// tax charged on top of sending amount
+    pub fn tax_on(&self, amount: Uint128) -> Uint128 {
+        let tax = amount * self.rate;
+        match self.cap {
+            Some(cap) => std::cmp::min(tax, cap),
+            None => tax,
+        }
+    }
+
+    /// budget to hand deduct for amount to arrive in full
+    pub fn gross_up(&self, amount: Uint128) -> Uint128 {
+        if self.rate.is_zero() {
+            return amount;
+        }
+        // one above the rounded down tax, deduct rounds down again
+        let tax = amount * self.rate + Uint128::new(1);
+        match self.cap {
+            Some(cap) => amount + std::cmp::min(tax, cap),
+            None => amount + tax,
+        }
+    }
+
+    /// largest amount that can be sent, tax included, out of budget
+    pub fn deduct(&self, budget: Uint128) -> Uint128 {
+        let rate = Decimal::one() + self.rate;
+        let uncapped = budget.multiply_ratio(rate.denominator(), rate.numerator());
+        let tax = budget - uncapped;
+        match self.cap {
+            Some(cap) if tax > cap => budget - cap,
+            _ => uncapped,
+        }
+    }
+}
+
+/// A registered affiliate, stored under its code
+#[cw_serde]
+pub struct Affiliate {
+    pub addr: Addr,
+    // lifetime reserve denom sent by buyers using the code
+    pub volume: Uint128,
+    pub referrals: u64,
+    pub rewards_earned: Uint128,
+    pub rewards_claimed: Uint128,
+}
+
+/// Owner rules on who may be credited as affiliate
+#[cw_serde]
+#[derive(Default)]
+pub struct AffiliateRules {
+    // once a buyer used a code, later buys are credited to it whatever code they pass
+    pub sticky_referrer: bool,
+}
+
+#[cw_serde]
+pub struct CircuitBreakerConfig {
+    // largest move allowed within the window, per 1000, 0 for no check
+    pub max_price_move_percent: u32,
+    pub max_reserve_move_percent: u32,
+    pub window: BreakerWindow,
+    // seconds trading stays stopped after a trip
+    pub cooldown_seconds: u64,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        CircuitBreakerConfig {
+            max_price_move_percent: 0,
+            max_reserve_move_percent: 0,
+            window: BreakerWindow::Seconds(0),
+            cooldown_seconds: 0,
+        }
+    }
+}
+
+/// Limits on reserve sent to buy, in reserve denom
+#[cw_serde]
+#[derive(Default)]
+pub struct BuyLimits {
+    pub min_buy: Uint128,
+    pub max_buy: Option<Uint128>,
+    // per address, in a 24h window from its first buy
+    pub address_daily_cap: Option<Uint128>,
+    pub block_cap: Option<Uint128>,
+}
+
+/// Reserve spent since window_start, by block time in seconds or block height
+#[cw_serde]
+#[derive(Default)]
+pub struct BuyUsage {
+    pub window_start: u64,
+    pub amount: Uint128,
+}
+
+/// Sells within cooldown_seconds of a buy are refused, or taxed at early_exit_tax_percent
+#[cw_serde]
+#[derive(Default)]
+pub struct CooldownConfig {
+    pub cooldown_seconds: u64,
+    pub early_exit_tax_percent: Option<u32>,
+}
+
+/// Anti-whale cap on balances
+#[cw_serde]
+#[derive(Default)]
+pub struct WhaleConfig {
+    // largest balance per 1000 of supply, 0 for no cap
+    pub max_balance_percent: u32,
+    // no cap while the supply is below this, so the first buyers can get in
+    #[serde(default)]
+    pub min_supply: Uint128,
+}
+
+/// Spot price and reserve after a trade
+#[cw_serde]
+pub struct PriceSample {
+    pub height: u64,
+    pub time: u64,
+    pub spot_price: Uint128,
+    pub reserve: Uint128,
+}
+
+#[cw_serde]
+pub struct BreakerTrip {
+    pub reason: String,
+    pub tripped_at: u64,
+    pub resets_at: u64,
+}
+
+/// Throttles DistributeTaxes
+#[cw_serde]
+#[derive(Default)]
+pub struct DistributionConfig {
+    // seconds that have to pass between two distributions, 0 for none
+    pub min_interval: u64,
+}
 
 pub const CURVE_STATE: Item<CurveState> = Item::new("curve_state");
 
@@ -80,8 +387,107 @@ pub const CURVE_TYPE: Item<CurveType> = Item::new("curve_type");
 
 pub const PARAM_CONFIG: Item<ParamConfig> = Item::new("param_config");
 
+pub const PARAM_CEILINGS: Item<ParamCeilings> = Item::new("param_ceilings");
+
 pub const ACCT_CONFIG: Item<AcctConfig> = Item::new("acct_config");
 
+pub const PENDING_OWNER: Item<OwnershipProposal> = Item::new("pending_owner");
+
+pub const TIMELOCK_CONFIG: Item<TimelockConfig> = Item::new("timelock_config");
+
+pub const PENDING_CHANGES: Map<u64, PendingChange> = Map::new("pending_changes");
+
+// last id handed to a pending change
+pub const PENDING_CHANGE_ID: Item<u64> = Item::new("pending_change_id");
+
+// admin roles held besides the owner, by address
+pub const ROLES: Map<&Addr, Vec<Role>> = Map::new("roles");
+
 pub const DEXFER_CONFIG: Item<DexferConfig> = Item::new("dexfer_config");
 
 pub const SAFETY_CONFIG: Item<SafetyConfig> = Item::new("safety_config");
+
+// paused actions, by PauseAction name
+pub const PAUSED_ACTIONS: Map<&str, bool> = Map::new("paused_actions");
+
+pub const BUY_LIMITS: Item<BuyLimits> = Item::new("buy_limits");
+
+// 24h buy usage by address, window_start is block time
+pub const ADDRESS_BUY_USAGE: Map<&Addr, BuyUsage> = Map::new("address_buy_usage");
+
+// buy usage of the last block with buys, window_start is its height
+pub const BLOCK_BUY_USAGE: Item<BuyUsage> = Item::new("block_buy_usage");
+
+pub const COOLDOWN_CONFIG: Item<CooldownConfig> = Item::new("cooldown_config");
+
+// block time in seconds of each address's last buy
+pub const LAST_BUY: Map<&Addr, u64> = Map::new("last_buy");
+
+pub const HOLDER_TIERS: Item<Vec<HolderTier>> = Item::new("holder_tiers");
+
+pub const HOLDING_TAX_SCHEDULE: Item<Vec<HoldingTaxStep>> = Item::new("holding_tax_schedule");
+
+// weighted average block time in seconds each holder acquired its balance at
+pub const ACQUIRED_AT: Map<&Addr, u64> = Map::new("acquired_at");
+
+// addresses barred from trading and moving BASE, with the reason
+pub const COMPLIANCE_DENYLIST: Map<&Addr, String> = Map::new("compliance_denylist");
+
+pub const WHALE_CONFIG: Item<WhaleConfig> = Item::new("whale_config");
+
+// addresses exempt from the balance cap
+pub const WHALE_WHITELIST: Map<&Addr, bool> = Map::new("whale_whitelist");
+
+pub const CIRCUIT_BREAKER_CONFIG: Item<CircuitBreakerConfig> = Item::new("circuit_breaker_config");
+
+// trades within the circuit breaker window, oldest first
+pub const PRICE_SAMPLES: Item<Vec<PriceSample>> = Item::new("price_samples");
+
+// set while the circuit breaker stops trading
+pub const BREAKER_TRIP: Item<BreakerTrip> = Item::new("breaker_trip");
+
+pub const RESERVE_CONFIG: Item<ReserveConfig> = Item::new("reserve_config");
+
+pub const LIQUIDITY_CONFIG: Item<LiquidityConfig> = Item::new("liquidity_config");
+
+pub const TAX_RECIPIENTS: Item<Vec<TaxRecipient>> = Item::new("tax_recipients");
+
+// same keys as PARAM_CONFIG and ACCT_CONFIG, for migrate
+pub const LEGACY_PARAM_CONFIG: Item<LegacyParamConfig> = Item::new("param_config");
+pub const LEGACY_ACCT_CONFIG: Item<LegacyAcctConfig> = Item::new("acct_config");
+pub const UNCHECKED_ACCT_CONFIG: Item<UncheckedAcctConfig> = Item::new("acct_config");
+pub const UNCHECKED_DEXFER_CONFIG: Item<UncheckedDexferConfig> = Item::new("dexfer_config");
+pub const LEGACY_SAFETY_CONFIG: Item<LegacySafetyConfig> = Item::new("safety_config");
+
+// lifetime tax paid, by recipient label
+pub const TAX_TOTALS: Map<&str, Uint128> = Map::new("tax_totals");
+
+// sell tax still bonded with the validator, by recipient label
+pub const SELL_TAX_ACCRUED: Map<&str, Uint128> = Map::new("sell_tax_owed");
+
+// tax held on the contract waiting for DistributeTaxes, by recipient label
+pub const TAX_PENDING: Map<&str, Uint128> = Map::new("tax_pending");
+
+pub const AFFILIATES: Map<&str, Affiliate> = Map::new("affiliates");
+
+// tiers above the base affiliate_percent, by rising min_volume
+pub const AFFILIATE_TIERS: Item<Vec<AffiliateTier>> = Item::new("affiliate_tiers");
+
+pub const AFFILIATE_RULES: Item<AffiliateRules> = Item::new("affiliate_rules");
+
+// first affiliate code each buyer used
+pub const REFERRERS: Map<&Addr, String> = Map::new("referrers");
+
+// affiliate addresses the owner barred from rewards
+pub const AFFILIATE_DENYLIST: Map<&Addr, bool> = Map::new("affiliate_denylist");
+
+// code registered by each affiliate address
+pub const AFFILIATE_CODES: Map<&Addr, String> = Map::new("affiliate_codes");
+
+pub const DISTRIBUTION_CONFIG: Item<DistributionConfig> = Item::new("distribution_config");
+
+// block time in seconds of the last DistributeTaxes
+pub const LAST_DISTRIBUTION: Item<u64> = Item::new("last_distribution");
+
+// used when the chain can't be queried for its tax
+pub const CHAIN_TAX_CONFIG: Item<ChainTaxConfig> = Item::new("chain_tax_config");
@@ -0,0 +1,114 @@
+use cosmwasm_std::testing::mock_env;
+use cosmwasm_std::{Addr, StdError};
+
+use crate::contract::migrate;
+use crate::msg::{AcctInfoResponse, DexferInfoResponse, ExecuteMsg, MigrateMsg, QueryMsg};
+use crate::state::{UncheckedAcctConfig, UncheckedDexferConfig, UNCHECKED_ACCT_CONFIG,
+	UNCHECKED_DEXFER_CONFIG};
+use crate::ContractError;
+use super::{exec, query_as, setup, OTHER, OWNER, STAKE_ACCT, UNSTAKE_ACCT};
+
+fn acct_config(presale_acct: Option<&str>, stake_acct: Option<&str>, unstake_acct: Option<&str>) -> ExecuteMsg {
+	ExecuteMsg::UpdateAcctConfig {
+		presale_acct: presale_acct.map(String::from),
+		stake_acct: stake_acct.map(String::from),
+		unstake_acct: unstake_acct.map(String::from),
+	}
+}
+
+#[test]
+fn update_acct_config_keeps_accounts_left_out() {
+	let mut deps = setup();
+	exec(&mut deps, mock_env(), OWNER, acct_config(Some("presale_acct"), None, None)).unwrap();
+
+	let accounts: AcctInfoResponse = query_as(&deps, QueryMsg::AcctInfo {});
+	assert_eq!(accounts.presale_acct, Some(Addr::unchecked("presale_acct")));
+	assert_eq!(accounts.stake_acct, Some(Addr::unchecked(STAKE_ACCT)));
+	assert_eq!(accounts.unstake_acct, Some(Addr::unchecked(UNSTAKE_ACCT)));
+}
+
+#[test]
+fn update_acct_config_unsets_on_empty_address() {
+	let mut deps = setup();
+	exec(&mut deps, mock_env(), OWNER, acct_config(None, Some(""), None)).unwrap();
+
+	let accounts: AcctInfoResponse = query_as(&deps, QueryMsg::AcctInfo {});
+	assert_eq!(accounts.stake_acct, None);
+	assert_eq!(accounts.unstake_acct, Some(Addr::unchecked(UNSTAKE_ACCT)));
+}
+
+#[test]
+fn update_acct_config_rejects_invalid_address() {
+	let mut deps = setup();
+	let err = exec(&mut deps, mock_env(), OWNER, acct_config(None, Some("x"), None)).unwrap_err();
+	assert!(matches!(err, ContractError::Std(StdError::GenericErr { .. })));
+
+	let err = exec(&mut deps, mock_env(), OTHER, acct_config(None, Some(OTHER), None)).unwrap_err();
+	assert_eq!(err, ContractError::MissingRole { role: "account_manager".to_string() });
+
+	// nothing changed
+	let accounts: AcctInfoResponse = query_as(&deps, QueryMsg::AcctInfo {});
+	assert_eq!(accounts.stake_acct, Some(Addr::unchecked(STAKE_ACCT)));
+}
+
+fn dexfer_config(dexfer_manager: Option<&str>, tax_exempt: Option<&str>) -> ExecuteMsg {
+	ExecuteMsg::UpdateDexferConfig {
+		dexfer_manager: dexfer_manager.map(String::from),
+		deposit_percent: 0,
+		tax_exempt: tax_exempt.map(String::from),
+		token_minter: "contract".to_string(),
+	}
+}
+
+#[test]
+fn update_dexfer_config_keeps_accounts_left_out() {
+	let mut deps = setup();
+	exec(&mut deps, mock_env(), OWNER, dexfer_config(Some("manager"), Some("exempt"))).unwrap();
+	exec(&mut deps, mock_env(), OWNER, dexfer_config(None, None)).unwrap();
+	let dexfer: DexferInfoResponse = query_as(&deps, QueryMsg::DexferInfo {});
+	assert_eq!(dexfer.dexfer_manager, Some(Addr::unchecked("manager")));
+	assert_eq!(dexfer.tax_exempt, Some(Addr::unchecked("exempt")));
+
+	exec(&mut deps, mock_env(), OWNER, dexfer_config(None, Some(""))).unwrap();
+	let dexfer: DexferInfoResponse = query_as(&deps, QueryMsg::DexferInfo {});
+	assert_eq!(dexfer.dexfer_manager, Some(Addr::unchecked("manager")));
+	assert_eq!(dexfer.tax_exempt, None);
+
+	let err = exec(&mut deps, mock_env(), OWNER, dexfer_config(Some("x"), None)).unwrap_err();
+	assert!(matches!(err, ContractError::Std(StdError::GenericErr { .. })));
+}
+
+#[test]
+fn migrate_drops_accounts_that_never_validated() {
+	let mut deps = setup();
+	// "x" is too short for the mock api, as "none" is for a real chain
+	UNCHECKED_ACCT_CONFIG.save(&mut deps.storage, &UncheckedAcctConfig {
+		owner: Some(Addr::unchecked(OWNER)),
+		presale_acct: Some("x".to_string()),
+		stake_acct: Some(STAKE_ACCT.to_string()),
+		unstake_acct: Some(UNSTAKE_ACCT.to_string()),
+	}).unwrap();
+	UNCHECKED_DEXFER_CONFIG.save(&mut deps.storage, &UncheckedDexferConfig {
+		dexfer_manager: Some("x".to_string()),
+		deposit_percent: 0,
+		tax_exempt: Some(OTHER.to_string()),
+		token_minter: String::new(),
+	}).unwrap();
+
+	migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+	let accounts: AcctInfoResponse = query_as(&deps, QueryMsg::AcctInfo {});
+	assert_eq!(accounts.presale_acct, None);
+	assert_eq!(accounts.stake_acct, Some(Addr::unchecked(STAKE_ACCT)));
+	let dexfer: DexferInfoResponse = query_as(&deps, QueryMsg::DexferInfo {});
+	assert_eq!(dexfer.dexfer_manager, None);
+	assert_eq!(dexfer.tax_exempt, Some(Addr::unchecked(OTHER)));
+}
+
+#[test]
+fn migrate_leaves_checked_accounts_alone() {
+	let mut deps = setup();
+	let before: AcctInfoResponse = query_as(&deps, QueryMsg::AcctInfo {});
+	migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+	let after: AcctInfoResponse = query_as(&deps, QueryMsg::AcctInfo {});
+	assert_eq!(before, after);
+}
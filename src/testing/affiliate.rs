@@ -0,0 +1,180 @@
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{coins, BankMsg, CosmosMsg, Response, StdError, Uint128};
+
+use crate::contract::execute;
+use crate::msg::{AffiliateInfoResponse, AffiliateTier, AffiliateTiersResponse, AffiliatesResponse,
+	ExecuteMsg, InstantiateParamConfig, QueryMsg};
+use crate::ContractError;
+use super::{attr_value, exec, instantiate_msg, query_as, setup, setup_with, TestDeps, BUYER, DENOM, OTHER,
+	OWNER};
+
+const AFFILIATE: &str = "affiliate";
+const CODE: &str = "ref-1";
+
+fn register(deps: &mut TestDeps, sender: &str) -> Result<Response, ContractError> {
+	exec(deps, mock_env(), sender, ExecuteMsg::RegisterAffiliate { code: CODE.to_string() })
+}
+
+fn referred_buy(deps: &mut TestDeps, amount: u128) -> Result<Response, ContractError> {
+	execute(
+		deps.as_mut(),
+		mock_env(),
+		mock_info(BUYER, &coins(amount, DENOM)),
+		ExecuteMsg::Buy { affiliate: CODE.to_string() },
+	)
+}
+
+/// Affiliates get 5% of what they refer
+fn rewarding_setup() -> TestDeps {
+	let mut msg = instantiate_msg();
+	msg.param_config = Some(InstantiateParamConfig {
+		affiliate_percent: 50,
+		tax_percent: 0,
+		presale_price: 0,
+	});
+	setup_with(msg)
+}
+
+#[test]
+fn affiliate_claims_accrued_rewards() {
+	let mut deps = rewarding_setup();
+	register(&mut deps, AFFILIATE).unwrap();
+	let res = referred_buy(&mut deps, 1000).unwrap();
+	// nothing is paid out on the buy
+	assert!(!res.messages.iter().any(|m| m.msg == CosmosMsg::Bank(BankMsg::Send {
+		to_address: AFFILIATE.to_string(),
+		amount: coins(50, DENOM),
+	})));
+
+	let info: AffiliateInfoResponse = query_as(&deps, QueryMsg::AffiliateInfo { code: CODE.to_string() });
+	assert_eq!(info.rewards_earned, Uint128::new(50));
+	assert_eq!(info.rewards_claimed, Uint128::zero());
+
+	let res = exec(&mut deps, mock_env(), AFFILIATE, ExecuteMsg::ClaimAffiliateRewards {}).unwrap();
+	assert_eq!(res.messages[0].msg, CosmosMsg::Bank(BankMsg::Send {
+		to_address: AFFILIATE.to_string(),
+		amount: coins(50, DENOM),
+	}));
+	let info: AffiliateInfoResponse = query_as(&deps, QueryMsg::AffiliateInfo { code: CODE.to_string() });
+	assert_eq!(info.rewards_claimed, Uint128::new(50));
+
+	let list: AffiliatesResponse = query_as(&deps, QueryMsg::Affiliates { start_after: None, limit: None });
+	assert_eq!(list.affiliates.iter().map(|a| a.code.as_str()).collect::<Vec<_>>(), vec![CODE]);
+}
+
+#[test]
+fn affiliate_registry_rejects_bad_registrations_and_claims() {
+	let mut deps = rewarding_setup();
+	let err = exec(&mut deps, mock_env(), AFFILIATE, ExecuteMsg::RegisterAffiliate {
+		code: "a b".to_string(),
+	}).unwrap_err();
+	assert_eq!(err, ContractError::InvalidAffiliateCode { code: "a b".to_string() });
+
+	register(&mut deps, AFFILIATE).unwrap();
+	let err = register(&mut deps, OTHER).unwrap_err();
+	assert_eq!(err, ContractError::AffiliateCodeTaken { code: CODE.to_string() });
+	let err = exec(&mut deps, mock_env(), AFFILIATE, ExecuteMsg::RegisterAffiliate {
+		code: "ref-2".to_string(),
+	}).unwrap_err();
+	assert_eq!(err, ContractError::AlreadyAffiliate { code: CODE.to_string() });
+
+	let err = exec(&mut deps, mock_env(), OTHER, ExecuteMsg::ClaimAffiliateRewards {}).unwrap_err();
+	assert_eq!(err, ContractError::NotAffiliate {});
+	let err = execute(
+		deps.as_mut(),
+		mock_env(),
+		mock_info(BUYER, &coins(1000, DENOM)),
+		ExecuteMsg::Buy { affiliate: "nobody".to_string() },
+	).unwrap_err();
+	assert_eq!(err, ContractError::UnknownAffiliate { code: "nobody".to_string() });
+}
+
+#[test]
+fn referred_buy_counts_for_the_affiliate() {
+	let mut deps = setup();
+	register(&mut deps, AFFILIATE).unwrap();
+	referred_buy(&mut deps, 1000).unwrap();
+
+	let info: AffiliateInfoResponse = query_as(&deps, QueryMsg::AffiliateInfo { code: CODE.to_string() });
+	assert_eq!(info.addr.as_str(), AFFILIATE);
+	assert_eq!(info.referrals, 1);
+	assert_eq!(info.volume, Uint128::new(1000));
+}
+
+#[test]
+fn contracts_cannot_register_as_affiliates() {
+	let mut deps = setup();
+	deps.querier.with_contracts(&[AFFILIATE]);
+	let err = register(&mut deps, AFFILIATE).unwrap_err();
+	assert_eq!(err, ContractError::ContractReferral { code: CODE.to_string() });
+}
+
+#[test]
+fn referred_buy_rejects_contract_affiliates() {
+	let mut deps = setup();
+	register(&mut deps, AFFILIATE).unwrap();
+	// instantiated at an address registered before it
+	deps.querier.with_contracts(&[AFFILIATE]);
+
+	let err = referred_buy(&mut deps, 1000).unwrap_err();
+	assert_eq!(err, ContractError::ContractReferral { code: CODE.to_string() });
+}
+
+#[test]
+fn referred_buy_fails_closed_without_contract_info() {
+	let mut deps = setup();
+	register(&mut deps, AFFILIATE).unwrap();
+	deps.querier.with_wasm_down(true);
+
+	let err = referred_buy(&mut deps, 1000).unwrap_err();
+	assert!(matches!(err, ContractError::Std(StdError::GenericErr { .. })));
+	let err = exec(&mut deps, mock_env(), BUYER, ExecuteMsg::RegisterAffiliate {
+		code: "ref-2".to_string(),
+	}).unwrap_err();
+	assert!(matches!(err, ContractError::Std(StdError::GenericErr { .. })));
+}
+
+fn tier(min_volume: u128, affiliate_percent: u32) -> AffiliateTier {
+	AffiliateTier { min_volume: Uint128::new(min_volume), affiliate_percent }
+}
+
+#[test]
+fn affiliate_moves_up_a_tier_with_volume() {
+	let mut deps = rewarding_setup();
+	exec(&mut deps, mock_env(), OWNER, ExecuteMsg::UpdateAffiliateTiers {
+		tiers: vec![tier(1000, 100), tier(5000, 150)],
+	}).unwrap();
+	let tiers: AffiliateTiersResponse = query_as(&deps, QueryMsg::AffiliateTiers {});
+	assert_eq!(tiers.base_percent, 50);
+	assert_eq!(tiers.tiers.len(), 2);
+	register(&mut deps, AFFILIATE).unwrap();
+
+	// paid from the volume before the buy
+	let res = referred_buy(&mut deps, 1000).unwrap();
+	assert_eq!(attr_value(&res, "Affiliate Tier: ").unwrap(), "0");
+	let res = referred_buy(&mut deps, 1000).unwrap();
+	assert_eq!(attr_value(&res, "Affiliate Tier: ").unwrap(), "1");
+
+	let info: AffiliateInfoResponse = query_as(&deps, QueryMsg::AffiliateInfo { code: CODE.to_string() });
+	assert_eq!(info.rewards_earned, Uint128::new(150));
+	assert_eq!(info.volume, Uint128::new(2000));
+	assert_eq!(info.tier, 1);
+	assert_eq!(info.affiliate_percent, 100);
+}
+
+#[test]
+fn affiliate_tiers_have_to_rise() {
+	let mut deps = rewarding_setup();
+	let err = exec(&mut deps, mock_env(), OWNER, ExecuteMsg::UpdateAffiliateTiers {
+		tiers: vec![tier(5000, 100), tier(5000, 150)],
+	}).unwrap_err();
+	assert_eq!(err, ContractError::InvalidAffiliateTiers {});
+	let err = exec(&mut deps, mock_env(), OWNER, ExecuteMsg::UpdateAffiliateTiers {
+		tiers: vec![tier(1000, 1001)],
+	}).unwrap_err();
+	assert_eq!(err, ContractError::InvalidPercentage { field: "affiliate_percent".to_string(), value: 1001 });
+	let err = exec(&mut deps, mock_env(), OTHER, ExecuteMsg::UpdateAffiliateTiers {
+		tiers: vec![tier(1000, 100)],
+	}).unwrap_err();
+	assert_eq!(err, ContractError::MissingRole { role: "fee_manager".to_string() });
+}
@@ -0,0 +1,77 @@
+use cosmwasm_std::testing::mock_env;
+
+use crate::msg::{BreakerWindow, CircuitBreakerStatusResponse, ExecuteMsg, QueryMsg};
+use crate::ContractError;
+use super::{attr_value, buy, env_after, exec, query_as, sell, setup, TestDeps, BUYER, OTHER, OWNER};
+
+const REASON: &str = "reserve moved more than 500 per 1000";
+
+/// Trips once the reserve moves more than half within a minute, for ten minutes
+fn tripped_setup() -> TestDeps {
+	let mut deps = setup();
+	exec(&mut deps, mock_env(), OWNER, ExecuteMsg::UpdateCircuitBreaker {
+		max_price_move_percent: 0,
+		max_reserve_move_percent: 500,
+		window: BreakerWindow::Seconds(60),
+		cooldown_seconds: 600,
+	}).unwrap();
+	buy(&mut deps, mock_env(), BUYER, 1000).unwrap();
+	// the trade that trips it still goes through
+	let res = buy(&mut deps, mock_env(), BUYER, 1000).unwrap();
+	assert_eq!(attr_value(&res, "Circuit Breaker: ").unwrap(), REASON);
+	deps
+}
+
+#[test]
+fn reserve_jump_trips_the_breaker_until_cooldown() {
+	let mut deps = tripped_setup();
+	let resets_at = mock_env().block.time.seconds() + 600;
+	let status: CircuitBreakerStatusResponse = query_as(&deps, QueryMsg::CircuitBreakerStatus {});
+	assert!(status.tripped);
+	assert_eq!(status.reason, Some(REASON.to_string()));
+	assert_eq!(status.resets_at, Some(resets_at));
+	// the reserve before the first buy, and after each of them
+	assert_eq!(status.samples, 3);
+
+	let tripped = ContractError::CircuitBreakerTripped { reason: REASON.to_string(), resets_at };
+	assert_eq!(buy(&mut deps, mock_env(), BUYER, 1000).unwrap_err(), tripped);
+	assert_eq!(sell(&mut deps, env_after(599), BUYER, 500).unwrap_err(), tripped);
+
+	sell(&mut deps, env_after(600), BUYER, 500).unwrap();
+}
+
+#[test]
+fn reset_clears_the_trip_for_pausers_only() {
+	let mut deps = tripped_setup();
+	let err = exec(&mut deps, mock_env(), OTHER, ExecuteMsg::ResetCircuitBreaker {}).unwrap_err();
+	assert_eq!(err, ContractError::MissingRole { role: "pauser".to_string() });
+
+	exec(&mut deps, mock_env(), OWNER, ExecuteMsg::ResetCircuitBreaker {}).unwrap();
+	let status: CircuitBreakerStatusResponse = query_as(&deps, QueryMsg::CircuitBreakerStatus {});
+	assert!(!status.tripped);
+	assert_eq!(status.samples, 0);
+	sell(&mut deps, mock_env(), BUYER, 500).unwrap();
+}
+
+#[test]
+fn single_trade_in_an_empty_window_is_measured() {
+	let mut deps = setup();
+	exec(&mut deps, mock_env(), OWNER, ExecuteMsg::UpdateCircuitBreaker {
+		max_price_move_percent: 0,
+		max_reserve_move_percent: 500,
+		window: BreakerWindow::Seconds(60),
+		cooldown_seconds: 600,
+	}).unwrap();
+	buy(&mut deps, mock_env(), BUYER, 1000).unwrap();
+
+	// the earlier trade has left the window
+	let res = buy(&mut deps, env_after(1000), BUYER, 999_000).unwrap();
+	assert_eq!(attr_value(&res, "Circuit Breaker: ").unwrap(), REASON);
+	let status: CircuitBreakerStatusResponse = query_as(&deps, QueryMsg::CircuitBreakerStatus {});
+	assert!(status.tripped);
+	let err = buy(&mut deps, env_after(1001), BUYER, 1000).unwrap_err();
+	assert_eq!(err, ContractError::CircuitBreakerTripped {
+		reason: REASON.to_string(),
+		resets_at: mock_env().block.time.seconds() + 1600,
+	});
+}
@@ -0,0 +1,80 @@
+use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{coin, coins, BankMsg, CosmosMsg, Uint128};
+
+use crate::contract::instantiate;
+use crate::msg::{CurveInfoResponse, ExecuteMsg, InstantiateParamConfig, QueryMsg, TaxRecipientMsg};
+use crate::ContractError;
+use super::mock_querier::mock_dependencies;
+use super::{buy, exec, instantiate_msg, query_as, setup, setup_with, TestDeps, BUYER, DENOM, OTHER,
+	OWNER, YIELD_ACCT};
+
+fn recipient(addr: Option<&str>, share_per_mille: u32, label: &str) -> TaxRecipientMsg {
+	TaxRecipientMsg {
+		addr: addr.map(String::from),
+		share_per_mille,
+		label: label.to_string(),
+	}
+}
+
+/// 10% tax, 60% of it to YIELD_ACCT and 40% burned
+fn burning_setup() -> TestDeps {
+	let mut msg = instantiate_msg();
+	msg.param_config = Some(InstantiateParamConfig {
+		affiliate_percent: 0,
+		tax_percent: 100,
+		presale_price: 0,
+	});
+	msg.tax_recipients = Some(vec![
+		recipient(Some(YIELD_ACCT), 600, "yield"),
+		recipient(None, 400, "burn"),
+	]);
+	setup_with(msg)
+}
+
+#[test]
+fn burn_share_is_burned_on_distribution() {
+	let mut deps = burning_setup();
+	buy(&mut deps, mock_env(), BUYER, 1000).unwrap();
+	deps.querier.with_balance(MOCK_CONTRACT_ADDR, &[coin(100, DENOM)]);
+
+	let res = exec(&mut deps, mock_env(), OTHER, ExecuteMsg::DistributeTaxes {}).unwrap();
+	let msgs: Vec<CosmosMsg> = res.messages.into_iter().map(|m| m.msg).collect();
+	assert_eq!(msgs, vec![
+		CosmosMsg::Bank(BankMsg::Send { to_address: YIELD_ACCT.to_string(), amount: coins(60, DENOM) }),
+		CosmosMsg::Bank(BankMsg::Burn { amount: coins(40, DENOM) }),
+	]);
+	let info: CurveInfoResponse = query_as(&deps, QueryMsg::CurveInfo {});
+	assert_eq!(info.reserve_burned, Uint128::new(40));
+	assert_eq!(info.tax_collected, Uint128::new(100));
+}
+
+#[test]
+fn tax_recipients_have_to_split_the_whole_tax() {
+	let mut deps = setup();
+	let err = exec(&mut deps, mock_env(), OWNER, ExecuteMsg::UpdateTaxRecipients {
+		recipients: vec![recipient(Some(YIELD_ACCT), 600, "yield"), recipient(None, 300, "burn")],
+	}).unwrap_err();
+	assert_eq!(err, ContractError::InvalidTaxSplit { total: 900 });
+
+	let err = exec(&mut deps, mock_env(), OWNER, ExecuteMsg::UpdateTaxRecipients {
+		recipients: vec![recipient(Some(YIELD_ACCT), 600, "burn"), recipient(None, 400, "burn")],
+	}).unwrap_err();
+	assert_eq!(err, ContractError::InvalidTaxLabel { label: "burn".to_string() });
+}
+
+#[test]
+fn tax_shares_cannot_wrap_around_to_the_whole_tax() {
+	let mut deps = setup();
+	// u32::MAX + 1001 wraps to 1000
+	let recipients = vec![recipient(Some(YIELD_ACCT), u32::MAX, "yield"), recipient(None, 1001, "burn")];
+	let err = exec(&mut deps, mock_env(), OWNER, ExecuteMsg::UpdateTaxRecipients {
+		recipients: recipients.clone(),
+	}).unwrap_err();
+	assert_eq!(err, ContractError::InvalidPercentage { field: "share_per_mille".to_string(), value: u32::MAX });
+
+	let mut msg = instantiate_msg();
+	msg.tax_recipients = Some(recipients);
+	let mut deps = mock_dependencies();
+	let err = instantiate(deps.as_mut(), mock_env(), mock_info(OWNER, &[]), msg).unwrap_err();
+	assert_eq!(err, ContractError::InvalidPercentage { field: "share_per_mille".to_string(), value: u32::MAX });
+}
@@ -0,0 +1,130 @@
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{coins, Addr, Response, StdError, Uint128};
+
+use crate::contract::execute;
+use crate::msg::{AffiliateInfoResponse, ComplianceDenylistResponse, DenylistEntry, DenylistEntryInfo,
+	ExecuteMsg, InstantiateParamConfig, QueryMsg};
+use crate::ContractError;
+use super::{buy, exec, instantiate_msg, query_as, sell, setup, setup_with, TestDeps, BUYER, DENOM,
+	OTHER, OWNER};
+
+const REASON: &str = "sanctioned";
+
+fn deny(deps: &mut TestDeps, sender: &str, address: &str) -> Result<(), ContractError> {
+	exec(deps, mock_env(), sender, ExecuteMsg::UpdateComplianceDenylist {
+		add: vec![DenylistEntry { address: address.to_string(), reason: REASON.to_string() }],
+		remove: vec![],
+	}).map(|_| ())
+}
+
+fn denylisted(rule: &str, address: &str) -> ContractError {
+	ContractError::Denylisted {
+		rule: rule.to_string(),
+		address: address.to_string(),
+		reason: REASON.to_string(),
+	}
+}
+
+fn transfer(deps: &mut TestDeps, sender: &str, recipient: &str) -> Result<(), ContractError> {
+	exec(deps, mock_env(), sender, ExecuteMsg::Transfer {
+		recipient: recipient.to_string(),
+		amount: Uint128::new(100),
+	}).map(|_| ())
+}
+
+fn referred_buy(deps: &mut TestDeps, code: &str) -> Result<Response, ContractError> {
+	execute(
+		deps.as_mut(),
+		mock_env(),
+		mock_info(BUYER, &coins(1000, DENOM)),
+		ExecuteMsg::Buy { affiliate: code.to_string() },
+	)
+}
+
+fn denylist(deps: &TestDeps, start_after: Option<&str>) -> Vec<DenylistEntryInfo> {
+	query_as::<ComplianceDenylistResponse>(deps, QueryMsg::ComplianceDenylist {
+		start_after: start_after.map(|s| s.to_string()),
+		limit: Some(1),
+	}).entries
+}
+
+#[test]
+fn denylisted_address_is_blocked_by_rule() {
+	let mut deps = setup();
+	buy(&mut deps, mock_env(), BUYER, 1000).unwrap();
+	buy(&mut deps, mock_env(), OTHER, 1000).unwrap();
+	deny(&mut deps, OWNER, BUYER).unwrap();
+
+	assert_eq!(buy(&mut deps, mock_env(), BUYER, 1000).unwrap_err(), denylisted("buyer", BUYER));
+	assert_eq!(sell(&mut deps, mock_env(), BUYER, 500).unwrap_err(), denylisted("seller", BUYER));
+	assert_eq!(transfer(&mut deps, BUYER, OTHER).unwrap_err(), denylisted("sender", BUYER));
+	assert_eq!(transfer(&mut deps, OTHER, BUYER).unwrap_err(), denylisted("recipient", BUYER));
+	let err = exec(&mut deps, mock_env(), OTHER, ExecuteMsg::IncreaseAllowance {
+		spender: BUYER.to_string(),
+		amount: Uint128::new(100),
+		expires: None,
+	}).unwrap_err();
+	assert_eq!(err, denylisted("spender", BUYER));
+
+	// lifting the block lets it trade again
+	exec(&mut deps, mock_env(), OWNER, ExecuteMsg::UpdateComplianceDenylist {
+		add: vec![],
+		remove: vec![BUYER.to_string()],
+	}).unwrap();
+	sell(&mut deps, mock_env(), BUYER, 500).unwrap();
+	transfer(&mut deps, OTHER, BUYER).unwrap();
+}
+
+#[test]
+fn compliance_denylist_pages_by_address() {
+	let mut deps = setup();
+	deny(&mut deps, OWNER, OTHER).unwrap();
+	deny(&mut deps, OWNER, BUYER).unwrap();
+
+	assert_eq!(denylist(&deps, None), vec![DenylistEntryInfo {
+		address: Addr::unchecked(BUYER),
+		reason: REASON.to_string(),
+	}]);
+	assert_eq!(denylist(&deps, Some(BUYER))[0].address, Addr::unchecked(OTHER));
+	assert!(denylist(&deps, Some(OTHER)).is_empty());
+}
+
+#[test]
+fn only_the_owner_updates_the_denylist() {
+	let mut deps = setup();
+	let err = deny(&mut deps, OTHER, BUYER).unwrap_err();
+	assert_eq!(err, ContractError::Unauthorized {});
+	let err = deny(&mut deps, OWNER, "x").unwrap_err();
+	assert!(matches!(err, ContractError::Std(StdError::GenericErr { .. })));
+}
+
+#[test]
+fn denylisted_affiliate_cannot_register_earn_or_claim() {
+	let mut msg = instantiate_msg();
+	msg.param_config = Some(InstantiateParamConfig {
+		affiliate_percent: 50,
+		tax_percent: 0,
+		presale_price: 0,
+	});
+	let mut deps = setup_with(msg);
+	exec(&mut deps, mock_env(), OTHER, ExecuteMsg::RegisterAffiliate { code: "ref-1".to_string() }).unwrap();
+	exec(&mut deps, mock_env(), OWNER, ExecuteMsg::UpdateAffiliateRules { sticky_referrer: true }).unwrap();
+	referred_buy(&mut deps, "ref-1").unwrap();
+
+	deny(&mut deps, OWNER, OTHER).unwrap();
+	deny(&mut deps, OWNER, "newcomer").unwrap();
+	let err = exec(&mut deps, mock_env(), "newcomer", ExecuteMsg::RegisterAffiliate {
+		code: "ref-2".to_string(),
+	}).unwrap_err();
+	assert_eq!(err, denylisted("affiliate", "newcomer"));
+	let err = exec(&mut deps, mock_env(), OTHER, ExecuteMsg::ClaimAffiliateRewards {}).unwrap_err();
+	assert_eq!(err, denylisted("claimant", OTHER));
+
+	// the sticky referrer is no longer credited, the buy itself goes through
+	referred_buy(&mut deps, "").unwrap();
+	let info: AffiliateInfoResponse = query_as(&deps, QueryMsg::AffiliateInfo { code: "ref-1".to_string() });
+	assert_eq!(info.rewards_earned, Uint128::new(50));
+	exec(&mut deps, mock_env(), OWNER, ExecuteMsg::UpdateAffiliateRules { sticky_referrer: false }).unwrap();
+	let err = referred_buy(&mut deps, "ref-1").unwrap_err();
+	assert_eq!(err, denylisted("affiliate", OTHER));
+}
@@ -0,0 +1,75 @@
+use cosmwasm_std::testing::mock_env;
+use cosmwasm_std::Uint128;
+
+use crate::msg::{CooldownResponse, ExecuteMsg, QueryMsg};
+use crate::ContractError;
+use super::{attr_value, buy, env_after, exec, query_as, sell, setup, taxed_setup, TestDeps, BUYER,
+	OTHER, OWNER};
+
+fn update_cooldown(deps: &mut TestDeps, sender: &str, early_exit_tax_percent: Option<u32>) -> Result<(), ContractError> {
+	exec(deps, mock_env(), sender, ExecuteMsg::UpdateCooldown {
+		cooldown_seconds: 3600,
+		early_exit_tax_percent,
+	}).map(|_| ())
+}
+
+#[test]
+fn cooldown_holds_sells_until_it_ends() {
+	let mut deps = setup();
+	update_cooldown(&mut deps, OWNER, None).unwrap();
+	buy(&mut deps, mock_env(), BUYER, 1000).unwrap();
+
+	let now = mock_env().block.time.seconds();
+	let cooldown: CooldownResponse = query_as(&deps, QueryMsg::Cooldown { address: BUYER.to_string() });
+	assert_eq!(cooldown.last_buy, Some(now));
+	assert_eq!(cooldown.ends_at, Some(now + 3600));
+
+	let err = sell(&mut deps, env_after(3599), BUYER, 500).unwrap_err();
+	assert_eq!(err, ContractError::SellCooldown { ends_at: now + 3600 });
+	sell(&mut deps, env_after(3600), BUYER, 500).unwrap();
+}
+
+#[test]
+fn early_exit_pays_the_higher_tax() {
+	let mut deps = taxed_setup(100);
+	update_cooldown(&mut deps, OWNER, Some(300)).unwrap();
+	buy(&mut deps, mock_env(), BUYER, 1000).unwrap();
+
+	let res = sell(&mut deps, mock_env(), BUYER, 500).unwrap();
+	assert_eq!(attr_value(&res, "LUNC Tax Accrued: ").unwrap(), "150");
+	assert_eq!(attr_value(&res, "Early Exit: ").unwrap(), "true");
+
+	let res = sell(&mut deps, env_after(3600), BUYER, 100).unwrap();
+	assert_eq!(attr_value(&res, "LUNC Tax Accrued: ").unwrap(), "10");
+	assert_eq!(attr_value(&res, "Early Exit: "), None);
+}
+
+#[test]
+fn update_cooldown_checks_the_early_exit_tax() {
+	let mut deps = setup();
+	let err = update_cooldown(&mut deps, OWNER, Some(1001)).unwrap_err();
+	assert_eq!(err, ContractError::InvalidPercentage {
+		field: "early_exit_tax_percent".to_string(),
+		value: 1001,
+	});
+	let err = update_cooldown(&mut deps, OTHER, None).unwrap_err();
+	assert_eq!(err, ContractError::MissingRole { role: "fee_manager".to_string() });
+}
+
+#[test]
+fn transfer_carries_the_cooldown_to_the_recipient() {
+	let mut deps = setup();
+	update_cooldown(&mut deps, OWNER, None).unwrap();
+	buy(&mut deps, mock_env(), BUYER, 1000).unwrap();
+	exec(&mut deps, mock_env(), BUYER, ExecuteMsg::Transfer {
+		recipient: OTHER.to_string(),
+		amount: Uint128::new(1000),
+	}).unwrap();
+
+	let ends_at = mock_env().block.time.seconds() + 3600;
+	let cooldown: CooldownResponse = query_as(&deps, QueryMsg::Cooldown { address: OTHER.to_string() });
+	assert_eq!(cooldown.ends_at, Some(ends_at));
+	let err = sell(&mut deps, mock_env(), OTHER, 500).unwrap_err();
+	assert_eq!(err, ContractError::SellCooldown { ends_at });
+	sell(&mut deps, env_after(3600), OTHER, 500).unwrap();
+}
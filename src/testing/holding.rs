@@ -0,0 +1,91 @@
+use cosmwasm_std::testing::mock_env;
+use cosmwasm_std::{Env, Uint128};
+
+use crate::msg::{ExecuteMsg, HoldingTaxResponse, HoldingTaxStep, QueryMsg};
+use crate::ContractError;
+use super::{attr_value, buy, env_after, exec, query_as, sell, taxed_setup, TestDeps, BUYER,
+	OTHER, OWNER};
+
+fn schedule(steps: &[(u64, u32)]) -> ExecuteMsg {
+	ExecuteMsg::UpdateHoldingTaxSchedule {
+		steps: steps
+		.iter()
+		.map(|&(max_age_seconds, tax_percent)| HoldingTaxStep { max_age_seconds, tax_percent })
+		.collect(),
+	}
+}
+
+/// 30% inside the first hour, the 10% base after
+fn scheduled_setup() -> TestDeps {
+	let mut deps = taxed_setup(100);
+	exec(&mut deps, mock_env(), OWNER, schedule(&[(3600, 300)])).unwrap();
+	deps
+}
+
+#[test]
+fn sell_right_after_a_buy_pays_the_first_step() {
+	let mut deps = scheduled_setup();
+	buy(&mut deps, mock_env(), BUYER, 1000).unwrap();
+
+	let holding: HoldingTaxResponse = query_as(&deps, QueryMsg::HoldingTax { address: Some(BUYER.to_string()) });
+	assert_eq!(holding.acquired_at, Some(mock_env().block.time.seconds()));
+	assert_eq!(holding.tax_percent, Some(300));
+
+	let res = sell(&mut deps, mock_env(), BUYER, 500).unwrap();
+	assert_eq!(attr_value(&res, "LUNC Tax Accrued: ").unwrap(), "150");
+}
+
+#[test]
+fn sell_past_the_schedule_pays_the_base_tax() {
+	let mut deps = scheduled_setup();
+	buy(&mut deps, mock_env(), BUYER, 1000).unwrap();
+
+	let res = sell(&mut deps, env_after(3600), BUYER, 500).unwrap();
+	assert_eq!(attr_value(&res, "LUNC Tax Accrued: ").unwrap(), "50");
+}
+
+#[test]
+fn holding_tax_schedule_has_to_rise() {
+	let mut deps = taxed_setup(100);
+	let err = exec(&mut deps, mock_env(), OWNER, schedule(&[(3600, 300), (3600, 200)])).unwrap_err();
+	assert_eq!(err, ContractError::InvalidHoldingTaxSchedule {});
+}
+
+fn transfer(deps: &mut TestDeps, env: Env, sender: &str, recipient: &str, amount: u128) {
+	exec(deps, env, sender, ExecuteMsg::Transfer {
+		recipient: recipient.to_string(),
+		amount: Uint128::new(amount),
+	}).unwrap();
+}
+
+fn acquired_at(deps: &TestDeps, address: &str) -> Option<u64> {
+	query_as::<HoldingTaxResponse>(deps, QueryMsg::HoldingTax { address: Some(address.to_string()) }).acquired_at
+}
+
+#[test]
+fn fresh_tokens_received_average_into_the_holding_time() {
+	let mut deps = scheduled_setup();
+	let start = mock_env().block.time.seconds();
+	buy(&mut deps, mock_env(), BUYER, 1000).unwrap();
+	buy(&mut deps, env_after(7200), OTHER, 1000).unwrap();
+	transfer(&mut deps, env_after(7200), OTHER, BUYER, 900);
+
+	// half held since start, half since two hours later
+	assert_eq!(acquired_at(&deps, BUYER), Some(start + 3600));
+	let res = sell(&mut deps, env_after(7199), BUYER, 100).unwrap();
+	assert_eq!(attr_value(&res, "LUNC Tax Accrued: ").unwrap(), "30");
+	let res = sell(&mut deps, env_after(7200), BUYER, 100).unwrap();
+	assert_eq!(attr_value(&res, "LUNC Tax Accrued: ").unwrap(), "10");
+}
+
+#[test]
+fn self_transfer_leaves_the_holding_time_alone() {
+	let mut deps = scheduled_setup();
+	let start = mock_env().block.time.seconds();
+	buy(&mut deps, mock_env(), BUYER, 1000).unwrap();
+	transfer(&mut deps, env_after(7200), BUYER, BUYER, 900);
+
+	assert_eq!(acquired_at(&deps, BUYER), Some(start));
+	let res = sell(&mut deps, env_after(7200), BUYER, 100).unwrap();
+	assert_eq!(attr_value(&res, "LUNC Tax Accrued: ").unwrap(), "10");
+}
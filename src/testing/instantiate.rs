@@ -0,0 +1,101 @@
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{coins, Addr, StdError};
+use cw20::MarketingInfoResponse;
+
+use crate::contract::{instantiate, PaymentError};
+use crate::msg::{AcctInfoResponse, DexferInfoResponse, InstantiateDexferConfig,
+	InstantiateMarketingInfo, InstantiateMsg, InstantiateParamConfig, ParamInfoResponse, QueryMsg,
+	SafetyInfoResponse, TaxRecipientMsg, TradingStatus};
+use crate::ContractError;
+use super::mock_querier::mock_dependencies;
+use super::{instantiate_msg, query_as, setup_with, BUYER, DENOM, OTHER, OWNER, STAKE_ACCT, YIELD_ACCT};
+
+fn try_instantiate(msg: InstantiateMsg) -> Result<(), ContractError> {
+	let mut deps = mock_dependencies();
+	instantiate(deps.as_mut(), mock_env(), mock_info(OWNER, &[]), msg).map(|_| ())
+}
+
+fn taxed_params(tax_percent: u32) -> Option<InstantiateParamConfig> {
+	Some(InstantiateParamConfig {
+		affiliate_percent: 20,
+		tax_percent,
+		presale_price: 0,
+	})
+}
+
+#[test]
+fn instantiate_applies_the_optional_configs() {
+	let mut msg = instantiate_msg();
+	msg.param_config = taxed_params(50);
+	msg.tax_recipients = Some(vec![TaxRecipientMsg {
+		addr: Some(YIELD_ACCT.to_string()),
+		share_per_mille: 1000,
+		label: "yield".to_string(),
+	}]);
+	msg.dexfer_config = Some(InstantiateDexferConfig {
+		dexfer_manager: Some(OTHER.to_string()),
+		deposit_percent: 100,
+		tax_exempt: None,
+		token_minter: "contract".to_string(),
+	});
+	msg.safety_config = Some(TradingStatus::SellOnly);
+	msg.marketing = Some(InstantiateMarketingInfo {
+		project: Some("base".to_string()),
+		description: None,
+		marketing: Some(BUYER.to_string()),
+	});
+	let deps = setup_with(msg);
+
+	let params: ParamInfoResponse = query_as(&deps, QueryMsg::ParamInfo {});
+	assert_eq!((params.affiliate_percent, params.tax_percent, params.presale_price), (20, 50, 0));
+	let accounts: AcctInfoResponse = query_as(&deps, QueryMsg::AcctInfo {});
+	assert_eq!(accounts.owner, Some(Addr::unchecked(OWNER)));
+	assert_eq!(accounts.stake_acct, Some(Addr::unchecked(STAKE_ACCT)));
+	let dexfer: DexferInfoResponse = query_as(&deps, QueryMsg::DexferInfo {});
+	assert_eq!(dexfer.dexfer_manager, Some(Addr::unchecked(OTHER)));
+	assert_eq!(dexfer.deposit_percent, 100);
+	assert_eq!(query_as::<SafetyInfoResponse>(&deps, QueryMsg::SafetyInfo {}).status, TradingStatus::SellOnly);
+	let marketing: MarketingInfoResponse = query_as(&deps, QueryMsg::MarketingInfo {});
+	assert_eq!(marketing.project, Some("base".to_string()));
+	assert_eq!(marketing.marketing, Some(Addr::unchecked(BUYER)));
+}
+
+#[test]
+fn instantiate_defaults_to_an_untaxed_presale_for_the_creator() {
+	let mut msg = instantiate_msg();
+	msg.param_config = None;
+	msg.acct_config = None;
+	msg.safety_config = None;
+	let deps = setup_with(msg);
+
+	let params: ParamInfoResponse = query_as(&deps, QueryMsg::ParamInfo {});
+	assert_eq!((params.affiliate_percent, params.tax_percent, params.presale_price), (0, 0, 200));
+	let accounts: AcctInfoResponse = query_as(&deps, QueryMsg::AcctInfo {});
+	assert_eq!(accounts.stake_acct, None);
+	let safety: SafetyInfoResponse = query_as(&deps, QueryMsg::SafetyInfo {});
+	assert_eq!(safety.status, TradingStatus::Allowlist(vec![Addr::unchecked(OWNER)]));
+}
+
+#[test]
+fn instantiate_rejects_invalid_configs() {
+	let mut msg = instantiate_msg();
+	msg.param_config = taxed_params(50);
+	assert_eq!(try_instantiate(msg).unwrap_err(), ContractError::NoTaxRecipients {});
+
+	let mut msg = instantiate_msg();
+	msg.param_config = taxed_params(1001);
+	assert_eq!(try_instantiate(msg).unwrap_err(), ContractError::InvalidPercentage {
+		field: "tax_percent".to_string(),
+		value: 1001,
+	});
+
+	let mut msg = instantiate_msg();
+	msg.safety_config = Some(TradingStatus::Allowlist(vec![Addr::unchecked("x")]));
+	let err = try_instantiate(msg).unwrap_err();
+	assert!(matches!(err, ContractError::Std(StdError::GenericErr { .. })));
+
+	let mut deps = mock_dependencies();
+	let err = instantiate(deps.as_mut(), mock_env(), mock_info(OWNER, &coins(10, DENOM)), instantiate_msg())
+	.unwrap_err();
+	assert_eq!(err, ContractError::Payment(PaymentError::NonPayable {}));
+}
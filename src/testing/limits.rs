@@ -0,0 +1,99 @@
+use cosmwasm_std::testing::mock_env;
+use cosmwasm_std::Uint128;
+
+use crate::msg::{ExecuteMsg, LimitsResponse, QueryMsg, Role};
+use crate::ContractError;
+use super::{buy, env_after, exec, query_as, setup, TestDeps, BUYER, OTHER, OWNER};
+
+fn buy_limits(min_buy: u128, max_buy: Option<u128>) -> ExecuteMsg {
+	ExecuteMsg::UpdateBuyLimits {
+		min_buy: Uint128::new(min_buy),
+		max_buy: max_buy.map(Uint128::new),
+		address_daily_cap: None,
+		block_cap: None,
+	}
+}
+
+#[test]
+fn fee_manager_sets_buy_limits() {
+	let mut deps = setup();
+	exec(&mut deps, mock_env(), OWNER, ExecuteMsg::UpdateRoles {
+		address: OTHER.to_string(),
+		roles: vec![Role::FeeManager],
+	}).unwrap();
+	exec(&mut deps, mock_env(), OTHER, buy_limits(100, Some(100))).unwrap();
+
+	let limits: LimitsResponse = query_as(&deps, QueryMsg::Limits { address: BUYER.to_string() });
+	assert_eq!(limits.min_buy, Uint128::new(100));
+	assert_eq!(limits.max_buy, Some(Uint128::new(100)));
+	buy(&mut deps, mock_env(), BUYER, 100).unwrap();
+}
+
+#[test]
+fn buy_limits_reject_min_above_max() {
+	let mut deps = setup();
+	let err = exec(&mut deps, mock_env(), OWNER, buy_limits(101, Some(100))).unwrap_err();
+	assert_eq!(err, ContractError::InvalidBuyLimits {
+		min_buy: Uint128::new(101),
+		max_buy: Uint128::new(100),
+	});
+
+	// the pauser no longer sets them
+	exec(&mut deps, mock_env(), OWNER, ExecuteMsg::UpdateRoles {
+		address: OTHER.to_string(),
+		roles: vec![Role::Pauser],
+	}).unwrap();
+	let err = exec(&mut deps, mock_env(), OTHER, buy_limits(0, None)).unwrap_err();
+	assert_eq!(err, ContractError::MissingRole { role: "fee_manager".to_string() });
+}
+
+fn caps(address_daily_cap: Option<u128>, block_cap: Option<u128>) -> ExecuteMsg {
+	ExecuteMsg::UpdateBuyLimits {
+		min_buy: Uint128::zero(),
+		max_buy: None,
+		address_daily_cap: address_daily_cap.map(Uint128::new),
+		block_cap: block_cap.map(Uint128::new),
+	}
+}
+
+fn limits(deps: &TestDeps, address: &str) -> LimitsResponse {
+	query_as(deps, QueryMsg::Limits { address: address.to_string() })
+}
+
+#[test]
+fn address_daily_cap_resets_after_a_day() {
+	let mut deps = setup();
+	exec(&mut deps, mock_env(), OWNER, caps(Some(1000), None)).unwrap();
+	buy(&mut deps, mock_env(), BUYER, 600).unwrap();
+
+	let used = limits(&deps, BUYER);
+	assert_eq!(used.address_used, Uint128::new(600));
+	assert_eq!(used.address_remaining, Some(Uint128::new(400)));
+	assert_eq!(used.address_resets_at, Some(mock_env().block.time.seconds() + 86400));
+	assert_eq!(used.block_remaining, None);
+
+	let err = buy(&mut deps, env_after(86399), BUYER, 500).unwrap_err();
+	assert_eq!(err, ContractError::AddressCapExceeded { remaining: Uint128::new(400) });
+	// the cap is per address
+	buy(&mut deps, mock_env(), OTHER, 1000).unwrap();
+	assert_eq!(limits(&deps, OTHER).address_remaining, Some(Uint128::zero()));
+
+	buy(&mut deps, env_after(86400), BUYER, 1000).unwrap();
+}
+
+#[test]
+fn block_cap_resets_on_the_next_height() {
+	let mut deps = setup();
+	exec(&mut deps, mock_env(), OWNER, caps(None, Some(1000))).unwrap();
+	buy(&mut deps, mock_env(), BUYER, 700).unwrap();
+
+	let used = limits(&deps, OTHER);
+	assert_eq!(used.block_used, Uint128::new(700));
+	assert_eq!(used.block_remaining, Some(Uint128::new(300)));
+	assert_eq!(used.address_remaining, None);
+
+	// shared by every buyer in the block
+	let err = buy(&mut deps, mock_env(), OTHER, 400).unwrap_err();
+	assert_eq!(err, ContractError::BlockCapExceeded { remaining: Uint128::new(300) });
+	buy(&mut deps, env_after(5), OTHER, 1000).unwrap();
+}
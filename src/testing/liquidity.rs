@@ -0,0 +1,64 @@
+use cosmwasm_std::testing::mock_env;
+use cosmwasm_std::{coins, BankMsg, CosmosMsg, Response, Uint128};
+
+use crate::msg::{ExecuteMsg, LiquidityInfoResponse, QueryMsg};
+use crate::ContractError;
+use super::{attr_value, buy, exec, query_as, setup, TestDeps, BUYER, DENOM, OTHER, OWNER, UNSTAKE_ACCT};
+
+fn liquidity_config(liquid_percent: u32, instant_fee_percent: u32) -> ExecuteMsg {
+	ExecuteMsg::UpdateLiquidityConfig { liquid_percent, instant_fee_percent }
+}
+
+fn instant_sell(deps: &mut TestDeps, amount: u128) -> Result<Response, ContractError> {
+	exec(deps, mock_env(), BUYER, ExecuteMsg::Burn { amount: Uint128::new(amount), instant: Some(true) })
+}
+
+/// BUYER bought 1000 with liquid_percent of it kept on the contract, 2% instant fee
+fn liquid_setup(liquid_percent: u32) -> TestDeps {
+	let mut deps = setup();
+	exec(&mut deps, mock_env(), OWNER, liquidity_config(liquid_percent, 20)).unwrap();
+	buy(&mut deps, mock_env(), BUYER, 1000).unwrap();
+	deps
+}
+
+#[test]
+fn instant_sell_pays_out_of_the_liquid_reserve() {
+	let mut deps = liquid_setup(500);
+	let info: LiquidityInfoResponse = query_as(&deps, QueryMsg::LiquidityInfo {});
+	assert_eq!(info.liquid_reserve, Uint128::new(500));
+	assert_eq!(info.target_reserve, Uint128::new(500));
+
+	let res = instant_sell(&mut deps, 100).unwrap();
+	assert_eq!(attr_value(&res, "Unstake Period: ").unwrap(), "Instant");
+	assert_eq!(attr_value(&res, "LUNC Instant Fee: ").unwrap(), "2");
+	assert_eq!(res.messages[0].msg, CosmosMsg::Bank(BankMsg::Send {
+		to_address: BUYER.to_string(),
+		amount: coins(98, DENOM),
+	}));
+	// the fee stays in the reserve
+	let info: LiquidityInfoResponse = query_as(&deps, QueryMsg::LiquidityInfo {});
+	assert_eq!(info.liquid_reserve, Uint128::new(402));
+}
+
+#[test]
+fn instant_sell_unbonds_when_the_buffer_is_short() {
+	let mut deps = liquid_setup(100);
+	let res = instant_sell(&mut deps, 500).unwrap();
+	assert_eq!(attr_value(&res, "Unstake Period: ").unwrap(), "21 Days");
+	assert_eq!(res.messages[0].msg, CosmosMsg::Bank(BankMsg::Send {
+		to_address: UNSTAKE_ACCT.to_string(),
+		amount: coins(101, DENOM),
+	}));
+	let info: LiquidityInfoResponse = query_as(&deps, QueryMsg::LiquidityInfo {});
+	assert_eq!(info.liquid_reserve, Uint128::new(100));
+}
+
+#[test]
+fn update_liquidity_config_checks_percentages() {
+	let mut deps = setup();
+	let err = exec(&mut deps, mock_env(), OWNER, liquidity_config(1001, 20)).unwrap_err();
+	assert_eq!(err, ContractError::InvalidPercentage { field: "liquid_percent".to_string(), value: 1001 });
+
+	let err = exec(&mut deps, mock_env(), OTHER, liquidity_config(500, 20)).unwrap_err();
+	assert_eq!(err, ContractError::MissingRole { role: "fee_manager".to_string() });
+}
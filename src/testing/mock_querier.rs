@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+
+use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+	from_slice, to_binary, Addr, Coin, ContractResult, Decimal, FullDelegation, OwnedDeps,
+	Querier, QuerierResult, QueryRequest, SystemError, SystemResult, Uint128, Validator,
+};
+use terra_cosmwasm::{ContractInfoResponse, TaxCapResponse, TaxRateResponse, TerraQuery,
+	TerraQueryWrapper};
+
+/// Deps answering the Terra treasury and wasm queries next to the usual bank and staking ones
+pub fn mock_dependencies() -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+	OwnedDeps {
+		storage: MockStorage::default(),
+		api: MockApi::default(),
+		querier: WasmMockQuerier::new(MockQuerier::new(&[(MOCK_CONTRACT_ADDR, &[])])),
+	}
+}
+
+pub struct WasmMockQuerier {
+	base: MockQuerier<TerraQueryWrapper>,
+	// None makes the treasury unreachable, as on a chain without the Terra bindings
+	tax_rate: Option<Decimal>,
+	tax_caps: HashMap<String, Uint128>,
+	contracts: Vec<String>,
+	// contract info queries fail at the system level, as when the wasm module can't be reached
+	wasm_down: bool,
+}
+
+impl Querier for WasmMockQuerier {
+	fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+		let request: QueryRequest<TerraQueryWrapper> = match from_slice(bin_request) {
+			Ok(v) => v,
+			Err(e) => {
+				return SystemResult::Err(SystemError::InvalidRequest {
+					error: format!("Parsing query request: {}", e),
+					request: bin_request.into(),
+				})
+			}
+		};
+		self.handle_query(&request)
+	}
+}
+
+impl WasmMockQuerier {
+	pub fn new(base: MockQuerier<TerraQueryWrapper>) -> Self {
+		WasmMockQuerier {
+			base,
+			tax_rate: Some(Decimal::zero()),
+			tax_caps: HashMap::new(),
+			contracts: vec![],
+			wasm_down: false,
+		}
+	}
+
+	pub fn handle_query(&self, request: &QueryRequest<TerraQueryWrapper>) -> QuerierResult {
+		match request {
+			QueryRequest::Custom(TerraQueryWrapper { query_data, .. }) => match query_data {
+				TerraQuery::TaxRate {} => match self.tax_rate {
+					Some(rate) => SystemResult::Ok(ContractResult::from(to_binary(&TaxRateResponse { rate }))),
+					None => SystemResult::Err(SystemError::UnsupportedRequest {
+						kind: "treasury".to_string(),
+					}),
+				},
+				TerraQuery::TaxCap { denom } => match self.tax_caps.get(denom) {
+					Some(cap) => SystemResult::Ok(ContractResult::from(to_binary(&TaxCapResponse { cap: *cap }))),
+					None => SystemResult::Ok(ContractResult::Err(format!("no tax cap for {}", denom))),
+				},
+				TerraQuery::ContractInfo { contract_address } => {
+					if self.wasm_down {
+						return SystemResult::Err(SystemError::UnsupportedRequest {
+							kind: "wasm".to_string(),
+						});
+					}
+					if !self.contracts.contains(contract_address) {
+						return SystemResult::Ok(ContractResult::Err(format!(
+							"contract {} not found",
+							contract_address
+						)));
+					}
+					SystemResult::Ok(ContractResult::from(to_binary(&ContractInfoResponse {
+						address: contract_address.clone(),
+						creator: "creator".to_string(),
+						code_id: 1,
+						admin: None,
+					})))
+				}
+				_ => SystemResult::Err(SystemError::UnsupportedRequest {
+					kind: "terra".to_string(),
+				}),
+			},
+			_ => self.base.handle_query(request),
+		}
+	}
+
+	pub fn with_tax(&mut self, rate: Option<Decimal>, caps: &[(&str, Uint128)]) {
+		self.tax_rate = rate;
+		self.tax_caps = caps.iter().map(|(denom, cap)| (denom.to_string(), *cap)).collect();
+	}
+
+	pub fn with_contracts(&mut self, contracts: &[&str]) {
+		self.contracts = contracts.iter().map(|c| c.to_string()).collect();
+	}
+
+	pub fn with_wasm_down(&mut self, down: bool) {
+		self.wasm_down = down;
+	}
+
+	pub fn with_balance(&mut self, addr: &str, balance: &[Coin]) {
+		self.base.update_balance(addr, balance.to_vec());
+	}
+
+	/// Delegations of delegator, one per validator
+	pub fn with_delegations(&mut self, delegator: &str, delegations: &[(&str, Coin)]) {
+		let validators: Vec<Validator> = delegations
+		.iter()
+		.map(|(validator, _)| Validator {
+			address: validator.to_string(),
+			commission: Decimal::zero(),
+			max_commission: Decimal::one(),
+			max_change_rate: Decimal::one(),
+		})
+		.collect();
+		let delegations: Vec<FullDelegation> = delegations
+		.iter()
+		.map(|(validator, amount)| FullDelegation {
+			delegator: Addr::unchecked(delegator),
+			validator: validator.to_string(),
+			amount: amount.clone(),
+			can_redelegate: amount.clone(),
+			accumulated_rewards: vec![],
+		})
+		.collect();
+		let denom = delegations.first().map(|d| d.amount.denom.clone()).unwrap_or_default();
+		self.base.update_staking(&denom, &validators, &delegations);
+	}
+}
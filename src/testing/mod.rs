@@ -0,0 +1,134 @@
+mod accounts;
+mod affiliate;
+mod breaker;
+mod burn;
+mod compliance;
+mod cooldown;
+mod holding;
+mod instantiate;
+mod limits;
+mod liquidity;
+mod mock_querier;
+mod ownership;
+mod params;
+mod pause;
+mod reserve;
+mod roles;
+mod status;
+mod tax;
+mod tiers;
+mod timelock;
+mod whale;
+
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage};
+use cosmwasm_std::{coins, from_binary, Env, OwnedDeps, Response, Uint128};
+use cw20::BalanceResponse;
+use serde::de::DeserializeOwned;
+
+use crate::contract::{execute, instantiate, query};
+use crate::msg::{CurveType, ExecuteMsg, InstantiateAcctConfig, InstantiateMsg,
+	InstantiateParamConfig, QueryMsg, TaxRecipientMsg, TradingStatus};
+use crate::ContractError;
+use mock_querier::{mock_dependencies, WasmMockQuerier};
+
+pub const OWNER: &str = "owner";
+pub const BUYER: &str = "buyer";
+pub const OTHER: &str = "other";
+pub const STAKE_ACCT: &str = "stake_acct";
+pub const UNSTAKE_ACCT: &str = "unstake_acct";
+pub const DENOM: &str = "uluna";
+pub const YIELD_ACCT: &str = "yield_acct";
+
+pub type TestDeps = OwnedDeps<MockStorage, MockApi, WasmMockQuerier>;
+
+/// Open trading on a 1:1 constant curve, untaxed and staking everything to STAKE_ACCT
+pub fn instantiate_msg() -> InstantiateMsg {
+	InstantiateMsg {
+		name: "Base Token".to_string(),
+		symbol: "BASE".to_string(),
+		decimals: 6,
+		reserve_denom: DENOM.to_string(),
+		reserve_decimals: 6,
+		curve_type: CurveType::Constant { value: Uint128::new(1), scale: 0 },
+		// no presale price, buys ride the curve
+		param_config: Some(InstantiateParamConfig {
+			affiliate_percent: 0,
+			tax_percent: 0,
+			presale_price: 0,
+		}),
+		acct_config: Some(InstantiateAcctConfig {
+			presale_acct: None,
+			stake_acct: Some(STAKE_ACCT.to_string()),
+			unstake_acct: Some(UNSTAKE_ACCT.to_string()),
+		}),
+		tax_recipients: None,
+		dexfer_config: None,
+		safety_config: Some(TradingStatus::Open),
+		marketing: None,
+		logo: None,
+	}
+}
+
+pub fn setup_with(msg: InstantiateMsg) -> TestDeps {
+	let mut deps = mock_dependencies();
+	instantiate(deps.as_mut(), mock_env(), mock_info(OWNER, &[]), msg).unwrap();
+	deps
+}
+
+pub fn setup() -> TestDeps {
+	setup_with(instantiate_msg())
+}
+
+/// Taxed at tax_percent, all of it going to YIELD_ACCT
+pub fn taxed_setup(tax_percent: u32) -> TestDeps {
+	let mut msg = instantiate_msg();
+	msg.param_config = Some(InstantiateParamConfig {
+		affiliate_percent: 0,
+		tax_percent,
+		presale_price: 0,
+	});
+	msg.tax_recipients = Some(vec![TaxRecipientMsg {
+		addr: Some(YIELD_ACCT.to_string()),
+		share_per_mille: 1000,
+		label: "yield".to_string(),
+	}]);
+	setup_with(msg)
+}
+
+/// mock_env moved seconds forward
+pub fn env_after(seconds: u64) -> Env {
+	let mut env = mock_env();
+	env.block.time = env.block.time.plus_seconds(seconds);
+	env.block.height += seconds / 5;
+	env
+}
+
+pub fn exec(deps: &mut TestDeps, env: Env, sender: &str, msg: ExecuteMsg) -> Result<Response, ContractError> {
+	execute(deps.as_mut(), env, mock_info(sender, &[]), msg)
+}
+
+pub fn buy(deps: &mut TestDeps, env: Env, sender: &str, amount: u128) -> Result<Response, ContractError> {
+	execute(
+		deps.as_mut(),
+		env,
+		mock_info(sender, &coins(amount, DENOM)),
+		ExecuteMsg::Buy { affiliate: String::new() },
+	)
+}
+
+pub fn sell(deps: &mut TestDeps, env: Env, sender: &str, amount: u128) -> Result<Response, ContractError> {
+	exec(deps, env, sender, ExecuteMsg::Burn { amount: Uint128::new(amount), instant: None })
+}
+
+pub fn balance(deps: &TestDeps, address: &str) -> Uint128 {
+	query_as::<BalanceResponse>(deps, QueryMsg::Balance { address: address.to_string() }).balance
+}
+
+pub fn query_as<T: DeserializeOwned>(deps: &TestDeps, msg: QueryMsg) -> T {
+	from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap()
+}
+
+/// Value of the first attribute named key
+pub fn attr_value(res: &Response, key: &str) -> Option<String> {
+	res.attributes.iter().find(|a| a.key == key).map(|a| a.value.clone())
+}
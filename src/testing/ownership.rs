@@ -0,0 +1,78 @@
+use cosmwasm_std::testing::mock_env;
+use cosmwasm_std::Addr;
+use cw20::Expiration;
+
+use crate::msg::{ExecuteMsg, PendingOwnerResponse, QueryMsg, Role, RolesResponse};
+use crate::ContractError;
+use super::{env_after, exec, query_as, setup, TestDeps, BUYER, OTHER, OWNER};
+
+fn propose(deps: &mut TestDeps, sender: &str, expiry: Option<Expiration>) -> Result<(), ContractError> {
+	exec(deps, mock_env(), sender, ExecuteMsg::ProposeNewOwner { owner: OTHER.to_string(), expiry }).map(|_| ())
+}
+
+#[test]
+fn proposed_owner_takes_over_on_accept() {
+	let mut deps = setup();
+	let expiry = Expiration::AtHeight(mock_env().block.height + 10);
+	propose(&mut deps, OWNER, Some(expiry)).unwrap();
+
+	let pending: PendingOwnerResponse = query_as(&deps, QueryMsg::PendingOwner {});
+	assert_eq!(pending.owner, Some(Addr::unchecked(OWNER)));
+	assert_eq!(pending.pending_owner, Some(Addr::unchecked(OTHER)));
+	assert_eq!(pending.expiry, Some(expiry));
+
+	exec(&mut deps, mock_env(), OTHER, ExecuteMsg::AcceptOwnership {}).unwrap();
+	let pending: PendingOwnerResponse = query_as(&deps, QueryMsg::PendingOwner {});
+	assert_eq!(pending.owner, Some(Addr::unchecked(OTHER)));
+	assert_eq!(pending.pending_owner, None);
+
+	// the old owner is out
+	let err = propose(&mut deps, OWNER, None).unwrap_err();
+	assert_eq!(err, ContractError::Unauthorized {});
+}
+
+#[test]
+fn ownership_proposal_rejects_others_and_expires() {
+	let mut deps = setup();
+	let err = propose(&mut deps, OTHER, None).unwrap_err();
+	assert_eq!(err, ContractError::Unauthorized {});
+	let err = exec(&mut deps, mock_env(), OTHER, ExecuteMsg::AcceptOwnership {}).unwrap_err();
+	assert_eq!(err, ContractError::NoOwnershipProposal {});
+
+	propose(&mut deps, OWNER, Some(Expiration::AtHeight(mock_env().block.height + 10))).unwrap();
+	let err = exec(&mut deps, mock_env(), BUYER, ExecuteMsg::AcceptOwnership {}).unwrap_err();
+	assert_eq!(err, ContractError::Unauthorized {});
+	let err = exec(&mut deps, env_after(50), OTHER, ExecuteMsg::AcceptOwnership {}).unwrap_err();
+	assert_eq!(err, ContractError::OwnershipProposalExpired {});
+
+	exec(&mut deps, mock_env(), OWNER, ExecuteMsg::CancelOwnershipProposal {}).unwrap();
+	let err = exec(&mut deps, mock_env(), OTHER, ExecuteMsg::AcceptOwnership {}).unwrap_err();
+	assert_eq!(err, ContractError::NoOwnershipProposal {});
+	let err = exec(&mut deps, mock_env(), OWNER, ExecuteMsg::CancelOwnershipProposal {}).unwrap_err();
+	assert_eq!(err, ContractError::NoOwnershipProposal {});
+}
+
+#[test]
+fn renounced_contract_has_no_admins_left() {
+	let mut deps = setup();
+	exec(&mut deps, mock_env(), OWNER, ExecuteMsg::UpdateRoles {
+		address: BUYER.to_string(),
+		roles: vec![Role::Pauser],
+	}).unwrap();
+	propose(&mut deps, OWNER, None).unwrap();
+	let err = exec(&mut deps, mock_env(), OTHER, ExecuteMsg::RenounceOwnership {}).unwrap_err();
+	assert_eq!(err, ContractError::Unauthorized {});
+
+	exec(&mut deps, mock_env(), OWNER, ExecuteMsg::RenounceOwnership {}).unwrap();
+	let pending: PendingOwnerResponse = query_as(&deps, QueryMsg::PendingOwner {});
+	assert_eq!(pending.owner, None);
+	assert_eq!(pending.pending_owner, None);
+	let roles: RolesResponse = query_as(&deps, QueryMsg::Roles { start_after: None, limit: None });
+	assert!(roles.assignments.is_empty());
+
+	let err = exec(&mut deps, mock_env(), OWNER, ExecuteMsg::UpdateRoles {
+		address: OWNER.to_string(),
+		roles: vec![Role::Pauser],
+	}).unwrap_err();
+	assert_eq!(err, ContractError::Unauthorized {});
+}
@@ -0,0 +1,154 @@
+use cosmwasm_std::testing::mock_env;
+use cosmwasm_std::{Addr, StdError};
+
+use crate::contract::migrate;
+use crate::msg::{ExecuteMsg, MigrateMsg, ParamInfoResponse, PendingChangesResponse, QueryMsg,
+	TaxRecipientMsg, TaxRecipientsResponse};
+use crate::state::{LegacyAcctConfig, LegacyParamConfig, LEGACY_ACCT_CONFIG, LEGACY_PARAM_CONFIG,
+	TAX_RECIPIENTS};
+use crate::ContractError;
+use super::{exec, query_as, setup, taxed_setup, TestDeps, OTHER, OWNER};
+
+fn param_config(tax_percent: u32) -> ExecuteMsg {
+	ExecuteMsg::UpdateParamConfig { affiliate_percent: 0, tax_percent, presale_price: 0 }
+}
+
+/// Rewinds storage to before the N-way tax split
+fn legacy_setup(yield_percent: u32, burn_percent: u32, social_percent: u32) -> TestDeps {
+	let mut deps = setup();
+	LEGACY_PARAM_CONFIG.save(&mut deps.storage, &LegacyParamConfig {
+		yield_percent,
+		burn_percent,
+		social_percent,
+		expense_percent: 0,
+		affiliate_percent: 0,
+		tax_percent: 50,
+		presale_price: 0,
+	}).unwrap();
+	LEGACY_ACCT_CONFIG.save(&mut deps.storage, &LegacyAcctConfig {
+		owner: Addr::unchecked(OWNER),
+		presale_acct: "presale_acct".to_string(),
+		yield_acct: "yield_acct".to_string(),
+		burn_acct: "burn_acct".to_string(),
+		social_acct: "social_acct".to_string(),
+		expense_acct: "expense_acct".to_string(),
+		stake_acct: "stake_acct".to_string(),
+		unstake_acct: "unstake_acct".to_string(),
+	}).unwrap();
+	TAX_RECIPIENTS.remove(&mut deps.storage);
+	deps
+}
+
+#[test]
+fn update_param_config_taxes_once_recipients_are_set() {
+	let mut deps = setup();
+	exec(&mut deps, mock_env(), OWNER, ExecuteMsg::UpdateTaxRecipients {
+		recipients: vec![TaxRecipientMsg {
+			addr: Some("yield_acct".to_string()),
+			share_per_mille: 1000,
+			label: "yield".to_string(),
+		}],
+	}).unwrap();
+
+	exec(&mut deps, mock_env(), OWNER, param_config(50)).unwrap();
+	let params: ParamInfoResponse = query_as(&deps, QueryMsg::ParamInfo {});
+	assert_eq!(params.tax_percent, 50);
+}
+
+#[test]
+fn update_param_config_rejects_tax_without_recipients() {
+	let mut deps = setup();
+	let err = exec(&mut deps, mock_env(), OWNER, param_config(50)).unwrap_err();
+	assert_eq!(err, ContractError::NoTaxRecipients {});
+
+	// untaxed is fine without anyone to pay
+	exec(&mut deps, mock_env(), OWNER, param_config(0)).unwrap();
+}
+
+fn lower_ceilings(max_tax_percent: u32, max_affiliate_percent: u32) -> ExecuteMsg {
+	ExecuteMsg::LowerParamCeilings { max_tax_percent, max_affiliate_percent }
+}
+
+#[test]
+fn lowered_ceilings_bound_the_params() {
+	let mut deps = taxed_setup(100);
+	exec(&mut deps, mock_env(), OWNER, lower_ceilings(200, 100)).unwrap();
+	let params: ParamInfoResponse = query_as(&deps, QueryMsg::ParamInfo {});
+	assert_eq!((params.max_tax_percent, params.max_affiliate_percent), (200, 100));
+
+	exec(&mut deps, mock_env(), OWNER, param_config(200)).unwrap();
+	let err = exec(&mut deps, mock_env(), OWNER, param_config(201)).unwrap_err();
+	assert_eq!(err, ContractError::AboveCeiling {
+		field: "tax_percent".to_string(),
+		value: 201,
+		ceiling: 200,
+	});
+}
+
+#[test]
+fn params_and_ceilings_reject_out_of_range_values() {
+	let mut deps = taxed_setup(100);
+	let err = exec(&mut deps, mock_env(), OWNER, param_config(1001)).unwrap_err();
+	assert_eq!(err, ContractError::InvalidPercentage { field: "tax_percent".to_string(), value: 1001 });
+
+	exec(&mut deps, mock_env(), OWNER, lower_ceilings(200, 100)).unwrap();
+	let err = exec(&mut deps, mock_env(), OWNER, lower_ceilings(300, 100)).unwrap_err();
+	assert_eq!(err, ContractError::CeilingRaised { field: "max_tax_percent".to_string(), ceiling: 200 });
+	// only the owner lowers them
+	let err = exec(&mut deps, mock_env(), OTHER, lower_ceilings(100, 100)).unwrap_err();
+	assert_eq!(err, ContractError::Unauthorized {});
+}
+
+#[test]
+fn migrate_gives_expense_the_rest_of_the_tax() {
+	let mut deps = legacy_setup(300, 200, 100);
+	migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+
+	let res: TaxRecipientsResponse = query_as(&deps, QueryMsg::TaxRecipients {});
+	let shares: Vec<(String, u32)> = res.recipients
+	.into_iter()
+	.map(|r| (r.label, r.share_per_mille))
+	.collect();
+	assert_eq!(shares, vec![
+		("yield".to_string(), 300),
+		("burn".to_string(), 200),
+		("social".to_string(), 100),
+		("expense".to_string(), 400),
+	]);
+	let params: ParamInfoResponse = query_as(&deps, QueryMsg::ParamInfo {});
+	assert_eq!(params.tax_percent, 50);
+}
+
+#[test]
+fn migrate_rejects_legacy_shares_over_the_whole_tax() {
+	let mut deps = legacy_setup(600, 300, 200);
+	let err = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap_err();
+	assert_eq!(err, StdError::generic_err(
+		"Legacy tax shares add up to 1100 per mille, more than the whole tax"
+	));
+}
+
+#[test]
+fn pending_changes_page_by_id() {
+	let mut deps = setup();
+	exec(&mut deps, mock_env(), OWNER, ExecuteMsg::UpdateTimelock { delay: 100 }).unwrap();
+	for _ in 0..3 {
+		exec(&mut deps, mock_env(), OWNER, param_config(0)).unwrap();
+	}
+
+	let page: PendingChangesResponse = query_as(&deps, QueryMsg::PendingChanges {
+		start_after: Some(1),
+		limit: None,
+	});
+	assert_eq!(page.delay, 100);
+	assert_eq!(page.changes.iter().map(|c| c.id).collect::<Vec<_>>(), vec![2, 3]);
+
+	exec(&mut deps, mock_env(), OWNER, ExecuteMsg::CancelPending { id: 2 }).unwrap();
+	let page: PendingChangesResponse = query_as(&deps, QueryMsg::PendingChanges {
+		start_after: None,
+		limit: Some(1),
+	});
+	assert_eq!(page.changes.iter().map(|c| c.id).collect::<Vec<_>>(), vec![1]);
+	let err = exec(&mut deps, mock_env(), OWNER, ExecuteMsg::ExecutePending { id: 2 }).unwrap_err();
+	assert_eq!(err, ContractError::NoPendingChange { id: 2 });
+}
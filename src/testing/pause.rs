@@ -0,0 +1,148 @@
+use cosmwasm_std::testing::mock_env;
+use cosmwasm_std::{Addr, Binary, Response, Uint128};
+
+use crate::msg::{ExecuteMsg, PauseAction, PauseStatusResponse, QueryMsg, TradingStatus};
+use crate::ContractError;
+use super::{buy, exec, query_as, setup, TestDeps, BUYER, OTHER, OWNER};
+
+fn set_pause(deps: &mut TestDeps, action: PauseAction, paused: bool) {
+	exec(deps, mock_env(), OWNER, ExecuteMsg::SetPause { action, paused }).unwrap();
+}
+
+fn allowlist(deps: &mut TestDeps, address: &str) {
+	exec(deps, mock_env(), OWNER, ExecuteMsg::UpdateSafetyConfig {
+		status: TradingStatus::Allowlist(vec![Addr::unchecked(address)]),
+	}).unwrap();
+}
+
+fn burn_from(deps: &mut TestDeps, amount: u128) -> Result<Response, ContractError> {
+	exec(deps, mock_env(), OTHER, ExecuteMsg::BurnFrom {
+		owner: BUYER.to_string(),
+		amount: Uint128::new(amount),
+		instant: None,
+	})
+}
+
+/// BUYER holds 1000 and lets OTHER sell all of it
+fn approved_setup() -> TestDeps {
+	let mut deps = setup();
+	buy(&mut deps, mock_env(), BUYER, 1000).unwrap();
+	exec(&mut deps, mock_env(), BUYER, ExecuteMsg::IncreaseAllowance {
+		spender: OTHER.to_string(),
+		amount: Uint128::new(1000),
+		expires: None,
+	}).unwrap();
+	deps
+}
+
+#[test]
+fn burn_from_follows_the_owner_status() {
+	let mut deps = approved_setup();
+	allowlist(&mut deps, BUYER);
+	burn_from(&mut deps, 400).unwrap();
+}
+
+#[test]
+fn burn_from_rejects_an_owner_that_may_not_sell() {
+	let mut deps = approved_setup();
+	// an allowed spender doesn't open up the owner's tokens
+	allowlist(&mut deps, OTHER);
+	let err = burn_from(&mut deps, 400).unwrap_err();
+	assert_eq!(err, ContractError::BurnPaused {});
+}
+
+#[test]
+fn payouts_can_be_paused() {
+	let mut deps = setup();
+	exec(&mut deps, mock_env(), OTHER, ExecuteMsg::DistributeTaxes {}).unwrap();
+
+	set_pause(&mut deps, PauseAction::DistributeTaxes, true);
+	set_pause(&mut deps, PauseAction::ClaimAffiliateRewards, true);
+	let err = exec(&mut deps, mock_env(), OTHER, ExecuteMsg::DistributeTaxes {}).unwrap_err();
+	assert_eq!(err, ContractError::ActionPaused { action: "distribute_taxes".to_string() });
+	let err = exec(&mut deps, mock_env(), OTHER, ExecuteMsg::ClaimAffiliateRewards {}).unwrap_err();
+	assert_eq!(err, ContractError::ActionPaused { action: "claim_affiliate_rewards".to_string() });
+
+	let status: PauseStatusResponse = query_as(&deps, QueryMsg::PauseStatus {});
+	let paused: Vec<PauseAction> = status.actions.iter().filter(|f| f.paused).map(|f| f.action).collect();
+	assert_eq!(paused, vec![PauseAction::ClaimAffiliateRewards, PauseAction::DistributeTaxes]);
+}
+
+#[test]
+fn each_flag_stops_its_path() {
+	let mut deps = approved_setup();
+	let amount = Uint128::new(100);
+	let paths = vec![
+		(PauseAction::Burn, BUYER, ExecuteMsg::Burn { amount, instant: None }),
+		(PauseAction::BurnFrom, OTHER, ExecuteMsg::BurnFrom { owner: BUYER.to_string(), amount, instant: None }),
+		(PauseAction::Transfer, BUYER, ExecuteMsg::Transfer { recipient: OTHER.to_string(), amount }),
+		(PauseAction::Send, BUYER, ExecuteMsg::Send {
+			contract: OTHER.to_string(),
+			amount,
+			msg: Binary::default(),
+		}),
+		(PauseAction::TransferFrom, OTHER, ExecuteMsg::TransferFrom {
+			owner: BUYER.to_string(),
+			recipient: OTHER.to_string(),
+			amount,
+		}),
+		(PauseAction::SendFrom, OTHER, ExecuteMsg::SendFrom {
+			owner: BUYER.to_string(),
+			contract: OTHER.to_string(),
+			amount,
+			msg: Binary::default(),
+		}),
+		(PauseAction::IncreaseAllowance, BUYER, ExecuteMsg::IncreaseAllowance {
+			spender: OTHER.to_string(),
+			amount,
+			expires: None,
+		}),
+		(PauseAction::DecreaseAllowance, BUYER, ExecuteMsg::DecreaseAllowance {
+			spender: OTHER.to_string(),
+			amount,
+			expires: None,
+		}),
+	];
+	for (action, sender, msg) in paths {
+		set_pause(&mut deps, action, true);
+		let err = exec(&mut deps, mock_env(), sender, msg.clone()).unwrap_err();
+		assert_eq!(err, ContractError::ActionPaused { action: action.as_str().to_string() });
+		set_pause(&mut deps, action, false);
+		exec(&mut deps, mock_env(), sender, msg).unwrap();
+	}
+
+	set_pause(&mut deps, PauseAction::Buy, true);
+	let err = buy(&mut deps, mock_env(), BUYER, 1000).unwrap_err();
+	assert_eq!(err, ContractError::ActionPaused { action: "buy".to_string() });
+	// the rest of the paths are unaffected
+	exec(&mut deps, mock_env(), BUYER, ExecuteMsg::Transfer { recipient: OTHER.to_string(), amount }).unwrap();
+}
+
+#[test]
+fn set_pause_leaves_an_audit_event() {
+	let mut deps = setup();
+	let toggle = |deps: &mut TestDeps, sender: &str, paused: bool| {
+		exec(deps, mock_env(), sender, ExecuteMsg::SetPause { action: PauseAction::Send, paused })
+	};
+	let event_attrs = |res: &Response| -> Vec<(String, String)> {
+		let event = res.events.iter().find(|e| e.ty == "pause").unwrap();
+		event.attributes.iter().map(|a| (a.key.clone(), a.value.clone())).collect()
+	};
+
+	let res = toggle(&mut deps, OWNER, true).unwrap();
+	assert_eq!(event_attrs(&res), vec![
+		("pause_action".to_string(), "send".to_string()),
+		("paused".to_string(), "true".to_string()),
+		("was_paused".to_string(), "false".to_string()),
+		("by".to_string(), OWNER.to_string()),
+		("height".to_string(), mock_env().block.height.to_string()),
+	]);
+	let res = toggle(&mut deps, OWNER, false).unwrap();
+	assert_eq!(event_attrs(&res)[1..3], [
+		("paused".to_string(), "false".to_string()),
+		("was_paused".to_string(), "true".to_string()),
+	]);
+
+	let err = toggle(&mut deps, OTHER, true).unwrap_err();
+	assert_eq!(err, ContractError::MissingRole { role: "pauser".to_string() });
+}
@@ -0,0 +1,115 @@
+use cosmwasm_std::testing::mock_env;
+use cosmwasm_std::{coin, Uint128};
+
+use crate::msg::{ExecuteMsg, InstantiateAcctConfig, QueryMsg, ReserveHealthResponse, Role,
+	SafetyInfoResponse, TradingStatus};
+use crate::ContractError;
+use super::{attr_value, buy, exec, instantiate_msg, query_as, sell, setup, setup_with, BUYER, DENOM,
+	OTHER, OWNER, STAKE_ACCT};
+
+fn reserve_config(tolerance_percent: u32, pause_sells: bool, mark_down: bool) -> ExecuteMsg {
+	ExecuteMsg::UpdateReserveConfig { tolerance_percent, pause_sells, mark_down }
+}
+
+#[test]
+fn sync_reserve_reports_healthy_reserve() {
+	let mut deps = setup();
+	buy(&mut deps, mock_env(), BUYER, 1000).unwrap();
+	// part of it is still waiting to be delegated
+	deps.querier.with_delegations(STAKE_ACCT, &[("validator", coin(600, DENOM))]);
+	deps.querier.with_balance(STAKE_ACCT, &[coin(400, DENOM)]);
+
+	let health: ReserveHealthResponse = query_as(&deps, QueryMsg::ReserveHealth {});
+	assert_eq!(health.stored_reserve, Uint128::new(1000));
+	assert_eq!(health.delegated, Uint128::new(600));
+	assert_eq!(health.liquid, Uint128::new(400));
+	assert!(health.healthy);
+
+	let res = exec(&mut deps, mock_env(), OTHER, ExecuteMsg::SyncReserve {}).unwrap();
+	assert_eq!(attr_value(&res, "Reserve Status: ").unwrap(), "healthy");
+}
+
+#[test]
+fn sync_reserve_leaves_mark_down_to_pauser() {
+	let mut deps = setup();
+	exec(&mut deps, mock_env(), OWNER, reserve_config(0, false, true)).unwrap();
+	buy(&mut deps, mock_env(), BUYER, 1000).unwrap();
+	// a validator got slashed
+	deps.querier.with_delegations(STAKE_ACCT, &[("validator", coin(900, DENOM))]);
+
+	let res = exec(&mut deps, mock_env(), OTHER, ExecuteMsg::SyncReserve {}).unwrap();
+	assert_eq!(attr_value(&res, "Shortfall: ").unwrap(), "100");
+	assert_eq!(attr_value(&res, "Reserve Marked Down: ").unwrap(), "needs owner or pauser");
+	let health: ReserveHealthResponse = query_as(&deps, QueryMsg::ReserveHealth {});
+	assert_eq!(health.stored_reserve, Uint128::new(1000));
+	assert_eq!(health.slashed_reserve, Uint128::zero());
+
+	exec(&mut deps, mock_env(), OWNER, ExecuteMsg::UpdateRoles {
+		address: OTHER.to_string(),
+		roles: vec![Role::Pauser],
+	}).unwrap();
+	let res = exec(&mut deps, mock_env(), OTHER, ExecuteMsg::SyncReserve {}).unwrap();
+	assert_eq!(attr_value(&res, "Reserve Marked Down: ").unwrap(), "100");
+	let health: ReserveHealthResponse = query_as(&deps, QueryMsg::ReserveHealth {});
+	assert_eq!(health.stored_reserve, Uint128::new(900));
+	assert_eq!(health.slashed_reserve, Uint128::new(100));
+	assert!(health.healthy);
+}
+
+#[test]
+fn sync_reserve_pauses_on_shortfall_above_tolerance() {
+	let mut deps = setup();
+	// 5% tolerated
+	exec(&mut deps, mock_env(), OWNER, reserve_config(50, true, false)).unwrap();
+	buy(&mut deps, mock_env(), BUYER, 1000).unwrap();
+
+	deps.querier.with_delegations(STAKE_ACCT, &[("validator", coin(960, DENOM))]);
+	exec(&mut deps, mock_env(), OTHER, ExecuteMsg::SyncReserve {}).unwrap();
+	let safety: SafetyInfoResponse = query_as(&deps, QueryMsg::SafetyInfo {});
+	assert_eq!(safety.status, TradingStatus::Open);
+
+	deps.querier.with_delegations(STAKE_ACCT, &[("validator", coin(940, DENOM))]);
+	let res = exec(&mut deps, mock_env(), OTHER, ExecuteMsg::SyncReserve {}).unwrap();
+	assert_eq!(attr_value(&res, "Sells Paused: ").unwrap(), "true");
+	let paused: Vec<String> = res.events.iter()
+	.filter(|e| e.ty == "pause")
+	.map(|e| e.attributes[0].value.clone())
+	.collect();
+	assert_eq!(paused, vec!["burn", "burn_from"]);
+
+	// buys and the trading status are left alone
+	let safety: SafetyInfoResponse = query_as(&deps, QueryMsg::SafetyInfo {});
+	assert_eq!(safety.status, TradingStatus::Open);
+	buy(&mut deps, mock_env(), BUYER, 100).unwrap();
+	let err = sell(&mut deps, mock_env(), BUYER, 100).unwrap_err();
+	assert_eq!(err, ContractError::ActionPaused { action: "burn".to_string() });
+}
+
+#[test]
+fn reserve_health_without_stake_account() {
+	let mut msg = instantiate_msg();
+	msg.acct_config = Some(InstantiateAcctConfig {
+		presale_acct: None,
+		stake_acct: None,
+		unstake_acct: None,
+	});
+	let deps = setup_with(msg);
+
+	let health: ReserveHealthResponse = query_as(&deps, QueryMsg::ReserveHealth {});
+	assert_eq!(health.delegated, Uint128::zero());
+	assert_eq!(health.liquid, Uint128::zero());
+	assert!(health.healthy);
+}
+
+#[test]
+fn update_reserve_config_needs_pauser() {
+	let mut deps = setup();
+	let err = exec(&mut deps, mock_env(), OTHER, reserve_config(0, true, true)).unwrap_err();
+	assert_eq!(err, ContractError::MissingRole { role: "pauser".to_string() });
+
+	let err = exec(&mut deps, mock_env(), OWNER, reserve_config(1001, true, true)).unwrap_err();
+	assert_eq!(err, ContractError::InvalidPercentage {
+		field: "tolerance_percent".to_string(),
+		value: 1001,
+	});
+}
@@ -0,0 +1,55 @@
+use cosmwasm_std::testing::mock_env;
+use cosmwasm_std::{Addr, Uint128};
+
+use crate::msg::{ExecuteMsg, QueryMsg, Role, RoleAssignment, RolesResponse, SafetyInfoResponse,
+	TradingStatus};
+use crate::ContractError;
+use super::{exec, query_as, setup, TestDeps, BUYER, OTHER, OWNER};
+
+fn assign(deps: &mut TestDeps, sender: &str, roles: Vec<Role>) -> Result<(), ContractError> {
+	exec(deps, mock_env(), sender, ExecuteMsg::UpdateRoles { address: OTHER.to_string(), roles }).map(|_| ())
+}
+
+fn sell_only(deps: &mut TestDeps) -> Result<(), ContractError> {
+	exec(deps, mock_env(), OTHER, ExecuteMsg::UpdateSafetyConfig { status: TradingStatus::SellOnly }).map(|_| ())
+}
+
+#[test]
+fn role_holder_can_use_its_role() {
+	let mut deps = setup();
+	assign(&mut deps, OWNER, vec![Role::Pauser, Role::Pauser]).unwrap();
+	let roles: RolesResponse = query_as(&deps, QueryMsg::Roles { start_after: None, limit: None });
+	assert_eq!(roles.assignments, vec![RoleAssignment {
+		address: Addr::unchecked(OTHER),
+		roles: vec![Role::Pauser],
+	}]);
+
+	sell_only(&mut deps).unwrap();
+	let safety: SafetyInfoResponse = query_as(&deps, QueryMsg::SafetyInfo {});
+	assert_eq!(safety.status, TradingStatus::SellOnly);
+}
+
+#[test]
+fn roles_do_not_reach_past_themselves() {
+	let mut deps = setup();
+	let err = assign(&mut deps, BUYER, vec![Role::Pauser]).unwrap_err();
+	assert_eq!(err, ContractError::Unauthorized {});
+	let err = sell_only(&mut deps).unwrap_err();
+	assert_eq!(err, ContractError::MissingRole { role: "pauser".to_string() });
+
+	assign(&mut deps, OWNER, vec![Role::Pauser]).unwrap();
+	let err = exec(&mut deps, mock_env(), OTHER, ExecuteMsg::UpdateBuyLimits {
+		min_buy: Uint128::new(10),
+		max_buy: None,
+		address_daily_cap: None,
+		block_cap: None,
+	}).unwrap_err();
+	assert_eq!(err, ContractError::MissingRole { role: "fee_manager".to_string() });
+
+	// no roles left clears the assignment
+	assign(&mut deps, OWNER, vec![]).unwrap();
+	let roles: RolesResponse = query_as(&deps, QueryMsg::Roles { start_after: None, limit: None });
+	assert!(roles.assignments.is_empty());
+	let err = sell_only(&mut deps).unwrap_err();
+	assert_eq!(err, ContractError::MissingRole { role: "pauser".to_string() });
+}
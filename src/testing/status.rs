@@ -0,0 +1,80 @@
+use cosmwasm_std::testing::mock_env;
+use cosmwasm_std::{Addr, StdError};
+
+use crate::contract::migrate;
+use crate::msg::{ExecuteMsg, MigrateMsg, QueryMsg, SafetyInfoResponse, TradingStatus};
+use crate::state::{LegacySafetyConfig, LEGACY_SAFETY_CONFIG};
+use crate::ContractError;
+use super::{buy, exec, query_as, sell, setup, TestDeps, BUYER, OTHER, OWNER};
+
+fn set_status(deps: &mut TestDeps, status: TradingStatus) -> Result<(), ContractError> {
+	exec(deps, mock_env(), OWNER, ExecuteMsg::UpdateSafetyConfig { status }).map(|_| ())
+}
+
+fn migrated_status(can_buy: &str, can_sell: &str) -> TradingStatus {
+	let mut deps = setup();
+	LEGACY_SAFETY_CONFIG.save(&mut deps.storage, &LegacySafetyConfig {
+		can_buy: can_buy.to_string(),
+		can_sell: can_sell.to_string(),
+	}).unwrap();
+	migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+	query_as::<SafetyInfoResponse>(&deps, QueryMsg::SafetyInfo {}).status
+}
+
+#[test]
+fn sell_only_still_lets_holders_out() {
+	let mut deps = setup();
+	buy(&mut deps, mock_env(), BUYER, 1000).unwrap();
+	set_status(&mut deps, TradingStatus::SellOnly).unwrap();
+	let safety: SafetyInfoResponse = query_as(&deps, QueryMsg::SafetyInfo {});
+	assert_eq!(safety.status, TradingStatus::SellOnly);
+
+	sell(&mut deps, mock_env(), BUYER, 500).unwrap();
+	let err = buy(&mut deps, mock_env(), BUYER, 1000).unwrap_err();
+	assert_eq!(err, ContractError::MintPaused {});
+}
+
+#[test]
+fn allowlist_lets_only_listed_addresses_trade() {
+	let mut deps = setup();
+	buy(&mut deps, mock_env(), OTHER, 1000).unwrap();
+	set_status(&mut deps, TradingStatus::Allowlist(vec![Addr::unchecked(BUYER)])).unwrap();
+
+	buy(&mut deps, mock_env(), BUYER, 1000).unwrap();
+	sell(&mut deps, mock_env(), BUYER, 500).unwrap();
+	let err = buy(&mut deps, mock_env(), OTHER, 1000).unwrap_err();
+	assert_eq!(err, ContractError::MintPaused {});
+	let err = sell(&mut deps, mock_env(), OTHER, 500).unwrap_err();
+	assert_eq!(err, ContractError::BurnPaused {});
+}
+
+#[test]
+fn paused_stops_buys_and_sells() {
+	let mut deps = setup();
+	buy(&mut deps, mock_env(), BUYER, 1000).unwrap();
+	set_status(&mut deps, TradingStatus::Paused).unwrap();
+
+	let err = buy(&mut deps, mock_env(), BUYER, 1000).unwrap_err();
+	assert_eq!(err, ContractError::MintPaused {});
+	let err = sell(&mut deps, mock_env(), BUYER, 500).unwrap_err();
+	assert_eq!(err, ContractError::BurnPaused {});
+}
+
+#[test]
+fn allowlist_rejects_invalid_addresses() {
+	let mut deps = setup();
+	let err = set_status(&mut deps, TradingStatus::Allowlist(vec![Addr::unchecked("x")])).unwrap_err();
+	assert!(matches!(err, ContractError::Std(StdError::GenericErr { .. })));
+	let err = exec(&mut deps, mock_env(), OTHER, ExecuteMsg::UpdateSafetyConfig {
+		status: TradingStatus::Paused,
+	}).unwrap_err();
+	assert_eq!(err, ContractError::MissingRole { role: "pauser".to_string() });
+}
+
+#[test]
+fn migrate_maps_the_legacy_flags() {
+	assert_eq!(migrated_status("1", "1"), TradingStatus::Open);
+	assert_eq!(migrated_status("0", "1"), TradingStatus::SellOnly);
+	assert_eq!(migrated_status("0", "0"), TradingStatus::Paused);
+	assert_eq!(migrated_status(BUYER, "0"), TradingStatus::Allowlist(vec![Addr::unchecked(BUYER)]));
+}
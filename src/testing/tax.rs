@@ -0,0 +1,167 @@
+use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{coin, coins, BankMsg, CosmosMsg, Decimal, Response, Uint128};
+
+use crate::contract::{execute, PaymentError};
+use crate::msg::{ChainTaxInfoResponse, ExecuteMsg, PendingTaxesResponse, QueryMsg,
+	TaxAccrualResponse, TaxAmount};
+use crate::ContractError;
+use super::{attr_value, buy, env_after, exec, query_as, sell, setup, taxed_setup, TestDeps, BUYER, DENOM,
+	OTHER, OWNER, STAKE_ACCT, UNSTAKE_ACCT, YIELD_ACCT};
+
+fn return_sell_tax(deps: &mut TestDeps, sender: &str, amount: u128) -> Result<Response, ContractError> {
+	execute(deps.as_mut(), mock_env(), mock_info(sender, &coins(amount, DENOM)), ExecuteMsg::ReturnSellTax {})
+}
+
+#[test]
+fn sell_tax_is_only_paid_once_returned() {
+	let mut deps = taxed_setup(100);
+	buy(&mut deps, mock_env(), BUYER, 1000).unwrap();
+	let res = sell(&mut deps, mock_env(), BUYER, 900).unwrap();
+	assert_eq!(attr_value(&res, "LUNC Tax Accrued: ").unwrap(), "90");
+
+	// the buy tax is on the contract, next to funds that aren't tax at all
+	deps.querier.with_balance(MOCK_CONTRACT_ADDR, &[coin(500, DENOM)]);
+	let res = exec(&mut deps, mock_env(), OTHER, ExecuteMsg::DistributeTaxes {}).unwrap();
+	assert_eq!(attr_value(&res, "LUNC Tax Paid: ").unwrap(), "100");
+	assert_eq!(res.messages.len(), 1);
+	assert_eq!(res.messages[0].msg, CosmosMsg::Bank(BankMsg::Send {
+		to_address: YIELD_ACCT.to_string(),
+		amount: coins(100, DENOM),
+	}));
+	let accrual: TaxAccrualResponse = query_as(&deps, QueryMsg::TaxAccrual {});
+	assert_eq!(accrual.accrued_total, Uint128::new(90));
+
+	let res = return_sell_tax(&mut deps, UNSTAKE_ACCT, 90).unwrap();
+	assert_eq!(attr_value(&res, "LUNC Tax Accrued: ").unwrap(), "0");
+	let pending: PendingTaxesResponse = query_as(&deps, QueryMsg::PendingTaxes {});
+	assert_eq!(pending.pending_total, Uint128::new(90));
+	assert_eq!(query_as::<TaxAccrualResponse>(&deps, QueryMsg::TaxAccrual {}).accrued_total, Uint128::zero());
+
+	deps.querier.with_balance(MOCK_CONTRACT_ADDR, &[coin(490, DENOM)]);
+	let res = exec(&mut deps, mock_env(), OTHER, ExecuteMsg::DistributeTaxes {}).unwrap();
+	assert_eq!(attr_value(&res, "LUNC Tax Paid: ").unwrap(), "90");
+	assert_eq!(attr_value(&res, "LUNC Tax Pending: ").unwrap(), "0");
+}
+
+#[test]
+fn return_sell_tax_rejects_more_than_accrued() {
+	let mut deps = taxed_setup(100);
+	buy(&mut deps, mock_env(), BUYER, 1000).unwrap();
+	sell(&mut deps, mock_env(), BUYER, 900).unwrap();
+
+	let err = return_sell_tax(&mut deps, UNSTAKE_ACCT, 91).unwrap_err();
+	assert_eq!(err, ContractError::SellTaxOverpaid { accrued: Uint128::new(90) });
+
+	let err = execute(
+		deps.as_mut(),
+		mock_env(),
+		mock_info(UNSTAKE_ACCT, &coins(90, "uusd")),
+		ExecuteMsg::ReturnSellTax {},
+	).unwrap_err();
+	assert_eq!(err, ContractError::Payment(PaymentError::MissingDenom(DENOM.to_string())));
+
+	// a partial return leaves the rest accrued
+	return_sell_tax(&mut deps, UNSTAKE_ACCT, 40).unwrap();
+	assert_eq!(query_as::<TaxAccrualResponse>(&deps, QueryMsg::TaxAccrual {}).accrued_total, Uint128::new(50));
+}
+
+#[test]
+fn chain_tax_is_read_from_the_chain() {
+	let mut deps = setup();
+	deps.querier.with_tax(Some(Decimal::permille(5)), &[(DENOM, Uint128::new(10))]);
+
+	let info: ChainTaxInfoResponse = query_as(&deps, QueryMsg::ChainTaxInfo {});
+	assert!(info.from_chain);
+	assert_eq!(info.rate, Decimal::permille(5));
+	assert_eq!(info.cap, Some(Uint128::new(10)));
+
+	// a denom without a cap on chain falls back to the owner set cap
+	exec(&mut deps, mock_env(), OWNER, ExecuteMsg::UpdateChainTaxConfig {
+		rate: Decimal::permille(7),
+		cap: Some(Uint128::new(3)),
+	}).unwrap();
+	deps.querier.with_tax(Some(Decimal::permille(5)), &[]);
+	let info: ChainTaxInfoResponse = query_as(&deps, QueryMsg::ChainTaxInfo {});
+	assert!(info.from_chain);
+	assert_eq!(info.rate, Decimal::permille(5));
+	assert_eq!(info.cap, Some(Uint128::new(3)));
+}
+
+#[test]
+fn chain_tax_falls_back_when_the_chain_does_not_answer() {
+	let mut deps = setup();
+	deps.querier.with_tax(None, &[]);
+
+	let info: ChainTaxInfoResponse = query_as(&deps, QueryMsg::ChainTaxInfo {});
+	assert!(!info.from_chain);
+	assert_eq!(info.rate, Decimal::permille(5));
+	assert_eq!(info.cap, None);
+
+	let err = exec(&mut deps, mock_env(), OTHER, ExecuteMsg::UpdateChainTaxConfig {
+		rate: Decimal::permille(7),
+		cap: None,
+	}).unwrap_err();
+	assert_eq!(err, ContractError::MissingRole { role: "fee_manager".to_string() });
+}
+
+#[test]
+fn stake_send_leaves_room_for_the_chain_tax() {
+	let mut deps = setup();
+	deps.querier.with_tax(Some(Decimal::percent(1)), &[]);
+	let res = buy(&mut deps, mock_env(), BUYER, 1000).unwrap();
+	assert_eq!(res.messages[0].msg, CosmosMsg::Bank(BankMsg::Send {
+		to_address: STAKE_ACCT.to_string(),
+		amount: coins(990, DENOM),
+	}));
+
+	// the cap bounds the tax set aside
+	deps.querier.with_tax(Some(Decimal::percent(1)), &[(DENOM, Uint128::new(5))]);
+	let res = buy(&mut deps, mock_env(), BUYER, 1000).unwrap();
+	assert_eq!(res.messages[0].msg, CosmosMsg::Bank(BankMsg::Send {
+		to_address: STAKE_ACCT.to_string(),
+		amount: coins(995, DENOM),
+	}));
+}
+
+#[test]
+fn unstake_marker_arrives_in_full() {
+	let mut deps = setup();
+	deps.querier.with_tax(Some(Decimal::permille(5)), &[]);
+	buy(&mut deps, mock_env(), BUYER, 1000).unwrap();
+
+	let res = sell(&mut deps, mock_env(), BUYER, 500).unwrap();
+	assert_eq!(res.messages[0].msg, CosmosMsg::Bank(BankMsg::Send {
+		to_address: UNSTAKE_ACCT.to_string(),
+		amount: coins(101, DENOM),
+	}));
+}
+
+#[test]
+fn buy_tax_waits_on_the_contract() {
+	let mut deps = taxed_setup(100);
+	let res = buy(&mut deps, mock_env(), BUYER, 1000).unwrap();
+	// only the stake send, no tax sent along
+	assert_eq!(res.messages.len(), 1);
+	assert_eq!(res.messages[0].msg, CosmosMsg::Bank(BankMsg::Send {
+		to_address: STAKE_ACCT.to_string(),
+		amount: coins(900, DENOM),
+	}));
+
+	let pending: PendingTaxesResponse = query_as(&deps, QueryMsg::PendingTaxes {});
+	assert_eq!(pending.pending, vec![TaxAmount { label: "yield".to_string(), amount: Uint128::new(100) }]);
+	assert_eq!(pending.pending_total, Uint128::new(100));
+}
+
+#[test]
+fn distribute_taxes_waits_out_min_interval() {
+	let mut deps = taxed_setup(100);
+	exec(&mut deps, mock_env(), OWNER, ExecuteMsg::UpdateDistributionConfig { min_interval: 3600 }).unwrap();
+	exec(&mut deps, mock_env(), OTHER, ExecuteMsg::DistributeTaxes {}).unwrap();
+
+	let next = mock_env().block.time.seconds() + 3600;
+	let pending: PendingTaxesResponse = query_as(&deps, QueryMsg::PendingTaxes {});
+	assert_eq!(pending.next_distribution, next);
+	let err = exec(&mut deps, env_after(3599), OTHER, ExecuteMsg::DistributeTaxes {}).unwrap_err();
+	assert_eq!(err, ContractError::DistributeTooSoon { next });
+	exec(&mut deps, env_after(3600), OTHER, ExecuteMsg::DistributeTaxes {}).unwrap();
+}
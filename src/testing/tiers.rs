@@ -0,0 +1,65 @@
+use cosmwasm_std::testing::mock_env;
+use cosmwasm_std::Uint128;
+
+use crate::contract::query;
+use crate::msg::{ExecuteMsg, HolderTier, QueryMsg, SimulateBuyResponse};
+use crate::ContractError;
+use super::{attr_value, balance, buy, exec, query_as, taxed_setup, TestDeps, BUYER, OTHER, OWNER};
+
+fn holder_tiers(tiers: &[(u128, u32)]) -> ExecuteMsg {
+	ExecuteMsg::UpdateHolderTiers {
+		tiers: tiers
+		.iter()
+		.map(|&(min_balance, discount_percent)| HolderTier {
+			min_balance: Uint128::new(min_balance),
+			discount_percent,
+		})
+		.collect(),
+	}
+}
+
+fn simulate_buy(deps: &TestDeps, buyer: &str, amount: u128) -> SimulateBuyResponse {
+	query_as(deps, QueryMsg::SimulateBuy { buyer: buyer.to_string(), amount: Uint128::new(amount) })
+}
+
+/// 10% buy tax, halved from 500 BASE held
+fn tiered_setup() -> TestDeps {
+	let mut deps = taxed_setup(100);
+	exec(&mut deps, mock_env(), OWNER, holder_tiers(&[(500, 500)])).unwrap();
+	deps
+}
+
+#[test]
+fn simulate_buy_applies_the_holder_tier() {
+	let mut deps = tiered_setup();
+	let sim = simulate_buy(&deps, BUYER, 1000);
+	assert_eq!((sim.holder_tier, sim.tax_percent), (0, 100));
+	assert_eq!(sim.tax_amount, Uint128::new(100));
+	assert_eq!(sim.minted, Uint128::new(900));
+	buy(&mut deps, mock_env(), BUYER, 1000).unwrap();
+
+	let sim = simulate_buy(&deps, BUYER, 1000);
+	assert_eq!((sim.holder_tier, sim.tax_percent), (1, 50));
+	assert_eq!(sim.tax_amount, Uint128::new(50));
+	assert_eq!(sim.minted, Uint128::new(950));
+	// somebody without BASE still pays the full tax
+	assert_eq!(simulate_buy(&deps, OTHER, 1000).tax_percent, 100);
+
+	let res = buy(&mut deps, mock_env(), BUYER, 1000).unwrap();
+	assert_eq!(attr_value(&res, "Holder Tier: ").unwrap(), "1");
+	assert_eq!(balance(&deps, BUYER), Uint128::new(900 + 950));
+}
+
+#[test]
+fn simulate_buy_rejects_invalid_buyer() {
+	let deps = tiered_setup();
+	let msg = QueryMsg::SimulateBuy { buyer: "x".to_string(), amount: Uint128::new(1000) };
+	assert!(query(deps.as_ref(), mock_env(), msg).is_err());
+}
+
+#[test]
+fn holder_tiers_have_to_rise() {
+	let mut deps = taxed_setup(100);
+	let err = exec(&mut deps, mock_env(), OWNER, holder_tiers(&[(500, 500), (500, 600)])).unwrap_err();
+	assert_eq!(err, ContractError::InvalidHolderTiers {});
+}
@@ -0,0 +1,79 @@
+use cosmwasm_std::testing::mock_env;
+use cosmwasm_std::Uint128;
+
+use crate::msg::{BreakerWindow, CircuitBreakerStatusResponse, ExecuteMsg, LimitsResponse,
+	ParamInfoResponse, PendingChangesResponse, QueryMsg, TaxRecipientMsg, WhaleInfoResponse};
+use crate::ContractError;
+use super::{env_after, exec, query_as, setup, TestDeps, BUYER, OWNER};
+
+fn timelocked_setup(delay: u64) -> TestDeps {
+	let mut deps = setup();
+	exec(&mut deps, mock_env(), OWNER, ExecuteMsg::UpdateTaxRecipients {
+		recipients: vec![TaxRecipientMsg {
+			addr: Some("yield_acct".to_string()),
+			share_per_mille: 1000,
+			label: "yield".to_string(),
+		}],
+	}).unwrap();
+	exec(&mut deps, mock_env(), OWNER, ExecuteMsg::UpdateTimelock { delay }).unwrap();
+	deps
+}
+
+fn pending_ids(deps: &TestDeps) -> Vec<u64> {
+	let res: PendingChangesResponse = query_as(deps, QueryMsg::PendingChanges { start_after: None, limit: None });
+	res.changes.iter().map(|c| c.id).collect()
+}
+
+#[test]
+fn safety_knobs_skip_the_timelock() {
+	let mut deps = timelocked_setup(100);
+	exec(&mut deps, mock_env(), OWNER, ExecuteMsg::UpdateBuyLimits {
+		min_buy: Uint128::new(10),
+		max_buy: None,
+		address_daily_cap: None,
+		block_cap: None,
+	}).unwrap();
+	exec(&mut deps, mock_env(), OWNER, ExecuteMsg::UpdateWhaleConfig {
+		max_balance_percent: 100,
+		min_supply: Uint128::zero(),
+	}).unwrap();
+	exec(&mut deps, mock_env(), OWNER, ExecuteMsg::UpdateCircuitBreaker {
+		max_price_move_percent: 200,
+		max_reserve_move_percent: 0,
+		window: BreakerWindow::Seconds(60),
+		cooldown_seconds: 600,
+	}).unwrap();
+	exec(&mut deps, mock_env(), OWNER, ExecuteMsg::UpdateReserveConfig {
+		tolerance_percent: 10,
+		pause_sells: true,
+		mark_down: false,
+	}).unwrap();
+
+	assert!(pending_ids(&deps).is_empty());
+	let limits: LimitsResponse = query_as(&deps, QueryMsg::Limits { address: BUYER.to_string() });
+	assert_eq!(limits.min_buy, Uint128::new(10));
+	let whale: WhaleInfoResponse = query_as(&deps, QueryMsg::WhaleInfo { start_after: None, limit: None });
+	assert_eq!(whale.max_balance_percent, 100);
+	let breaker: CircuitBreakerStatusResponse = query_as(&deps, QueryMsg::CircuitBreakerStatus {});
+	assert_eq!(breaker.max_price_move_percent, 200);
+}
+
+#[test]
+fn fee_changes_wait_out_the_timelock() {
+	let mut deps = timelocked_setup(100);
+	exec(&mut deps, mock_env(), OWNER, ExecuteMsg::UpdateParamConfig {
+		affiliate_percent: 0,
+		tax_percent: 50,
+		presale_price: 0,
+	}).unwrap();
+	assert_eq!(pending_ids(&deps), vec![1]);
+	assert_eq!(query_as::<ParamInfoResponse>(&deps, QueryMsg::ParamInfo {}).tax_percent, 0);
+
+	let ready_at = mock_env().block.time.seconds() + 100;
+	let err = exec(&mut deps, env_after(99), OWNER, ExecuteMsg::ExecutePending { id: 1 }).unwrap_err();
+	assert_eq!(err, ContractError::ChangeNotReady { id: 1, ready_at });
+
+	exec(&mut deps, env_after(100), OWNER, ExecuteMsg::ExecutePending { id: 1 }).unwrap();
+	assert_eq!(query_as::<ParamInfoResponse>(&deps, QueryMsg::ParamInfo {}).tax_percent, 50);
+	assert!(pending_ids(&deps).is_empty());
+}
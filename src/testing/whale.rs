@@ -0,0 +1,114 @@
+use cosmwasm_std::testing::mock_env;
+use cosmwasm_std::{Binary, Uint128};
+
+use crate::msg::{ExecuteMsg, QueryMsg, WhaleInfoResponse};
+use crate::ContractError;
+use super::{balance, buy, exec, query_as, setup, TestDeps, BUYER, OTHER, OWNER};
+
+const SPENDER: &str = "spender";
+const POOL: &str = "pool";
+
+/// 10% of supply, once there are 10000 BASE
+fn capped_setup(min_supply: u128) -> TestDeps {
+	let mut deps = setup();
+	exec(&mut deps, mock_env(), OWNER, ExecuteMsg::UpdateWhaleConfig {
+		max_balance_percent: 100,
+		min_supply: Uint128::new(min_supply),
+	}).unwrap();
+	deps
+}
+
+#[test]
+fn first_buy_from_zero_supply_is_not_capped() {
+	let mut deps = capped_setup(10_000);
+	buy(&mut deps, mock_env(), BUYER, 1000).unwrap();
+
+	let whale: WhaleInfoResponse = query_as(&deps, QueryMsg::WhaleInfo { start_after: None, limit: None });
+	assert_eq!(whale.min_supply, Uint128::new(10_000));
+	assert_eq!(whale.max_balance, None);
+}
+
+#[test]
+fn cap_applies_once_supply_reaches_min_supply() {
+	let mut deps = capped_setup(10_000);
+	buy(&mut deps, mock_env(), BUYER, 1000).unwrap();
+
+	let err = buy(&mut deps, mock_env(), OTHER, 9000).unwrap_err();
+	assert_eq!(err, ContractError::MaxBalanceExceeded {
+		holder: OTHER.to_string(),
+		max: Uint128::new(1000),
+	});
+}
+
+#[test]
+fn cap_without_min_supply_stops_the_first_buy() {
+	let mut deps = capped_setup(0);
+	let err = buy(&mut deps, mock_env(), BUYER, 1000).unwrap_err();
+	assert_eq!(err, ContractError::MaxBalanceExceeded {
+		holder: BUYER.to_string(),
+		max: Uint128::new(100),
+	});
+}
+
+/// BUYER holds 9000 and OTHER 1000 when the 10% cap is switched on
+fn holders_setup() -> TestDeps {
+	let mut deps = setup();
+	buy(&mut deps, mock_env(), BUYER, 9000).unwrap();
+	buy(&mut deps, mock_env(), OTHER, 1000).unwrap();
+	exec(&mut deps, mock_env(), OWNER, ExecuteMsg::UpdateWhaleConfig {
+		max_balance_percent: 100,
+		min_supply: Uint128::zero(),
+	}).unwrap();
+	deps
+}
+
+fn over_cap(holder: &str) -> ContractError {
+	ContractError::MaxBalanceExceeded { holder: holder.to_string(), max: Uint128::new(1000) }
+}
+
+fn transfer(recipient: &str, amount: u128) -> ExecuteMsg {
+	ExecuteMsg::Transfer { recipient: recipient.to_string(), amount: Uint128::new(amount) }
+}
+
+fn send(contract: &str, amount: u128) -> ExecuteMsg {
+	ExecuteMsg::Send { contract: contract.to_string(), amount: Uint128::new(amount), msg: Binary::default() }
+}
+
+#[test]
+fn transfers_cannot_push_the_recipient_over_the_cap() {
+	let mut deps = holders_setup();
+	assert_eq!(exec(&mut deps, mock_env(), BUYER, transfer(OTHER, 100)).unwrap_err(), over_cap(OTHER));
+
+	let mut deps = holders_setup();
+	assert_eq!(exec(&mut deps, mock_env(), BUYER, send(OTHER, 100)).unwrap_err(), over_cap(OTHER));
+
+	let mut deps = holders_setup();
+	exec(&mut deps, mock_env(), BUYER, ExecuteMsg::IncreaseAllowance {
+		spender: SPENDER.to_string(),
+		amount: Uint128::new(100),
+		expires: None,
+	}).unwrap();
+	let err = exec(&mut deps, mock_env(), SPENDER, ExecuteMsg::TransferFrom {
+		owner: BUYER.to_string(),
+		recipient: OTHER.to_string(),
+		amount: Uint128::new(100),
+	}).unwrap_err();
+	assert_eq!(err, over_cap(OTHER));
+}
+
+#[test]
+fn whitelisted_recipient_is_let_through() {
+	let mut deps = holders_setup();
+	exec(&mut deps, mock_env(), OWNER, ExecuteMsg::UpdateWhaleWhitelist {
+		add: vec![OTHER.to_string(), POOL.to_string()],
+		remove: vec![],
+	}).unwrap();
+	exec(&mut deps, mock_env(), BUYER, transfer(OTHER, 100)).unwrap();
+	exec(&mut deps, mock_env(), BUYER, send(POOL, 1100)).unwrap();
+	assert_eq!(balance(&deps, OTHER), Uint128::new(1100));
+	assert_eq!(balance(&deps, POOL), Uint128::new(1100));
+
+	let whale: WhaleInfoResponse = query_as(&deps, QueryMsg::WhaleInfo { start_after: None, limit: None });
+	assert_eq!(whale.max_balance, Some(Uint128::new(1000)));
+	assert_eq!(whale.whitelist.len(), 2);
+}